@@ -0,0 +1,169 @@
+//! Benchmarks the declaration and lookup of IDs.
+//!
+//! The time per element should stay constant as the number of IDs grows.
+
+#![allow(dead_code)] // the types are only converted to measure the conversion.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use xylem::{declare_schema, DefaultContext, Id, Identifiable, NoArgs, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Xylem)]
+#[xylem(expose = ZoneFrom, derive(Clone))]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Zone>,
+    next:   Option<Id<Schema, Zone>>,
+    target: Option<Target>,
+    spawns: Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = SpawnFrom, derive(Clone))]
+struct Spawn {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Spawn>,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+}
+
+/// A reference that is resolved in an untagged enum,
+/// so that each reference is recorded in a checkpoint.
+#[derive(Xylem)]
+#[xylem(expose = TargetFrom, derive(Clone), untagged)]
+enum Target {
+    Zone(Id<Schema, Zone>),
+    Name {
+        #[xylem(transform = name(String))]
+        name: String,
+    },
+}
+
+fn name(name: String) -> anyhow::Result<String> { Ok(name) }
+
+/// A zone that is declared in an untagged enum,
+/// so that each declaration is made in a checkpoint.
+#[derive(Xylem)]
+#[xylem(expose = EntryFrom, untagged)]
+enum Entry {
+    Zone(Zone),
+}
+
+const SIZES: &[usize] = &[1_000, 10_000, 50_000];
+
+/// Creates `size` zones, each referencing the previous zone.
+fn zones(size: usize) -> Vec<ZoneFrom> {
+    (0..size)
+        .map(|index| ZoneFrom {
+            id:     format!("zone{}", index),
+            next:   index.checked_sub(1).map(|prev| format!("zone{}", prev)),
+            target: None,
+            spawns: Vec::new(),
+        })
+        .collect()
+}
+
+/// Creates `size` zones, each referencing the previous zone through an untagged enum.
+fn untagged_zones(size: usize) -> Vec<ZoneFrom> {
+    (0..size)
+        .map(|index| ZoneFrom {
+            id:     format!("zone{}", index),
+            next:   None,
+            target: index.checked_sub(1).map(|prev| TargetFrom(format!("zone{}", prev))),
+            spawns: Vec::new(),
+        })
+        .collect()
+}
+
+/// Creates `size` zones like [`zones`], each declared through an untagged enum.
+fn untagged_entries(size: usize) -> Vec<EntryFrom> {
+    zones(size).into_iter().map(EntryFrom).collect()
+}
+
+/// Creates a zone with `size` spawns.
+fn spawns(size: usize) -> Vec<ZoneFrom> {
+    vec![ZoneFrom {
+        id:     String::from("zone"),
+        next:   None,
+        target: None,
+        spawns: (0..size).map(|index| SpawnFrom { id: format!("spawn{}", index) }).collect(),
+    }]
+}
+
+fn bench_ids(c: &mut Criterion) {
+    let mut group = c.benchmark_group("declare_and_reference");
+    for &size in SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter_batched(
+                || zones(size),
+                |from| {
+                    let mut context = DefaultContext::default();
+                    Vec::<Zone>::convert(from, &mut context, &NoArgs).unwrap()
+                },
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("reference_untagged");
+    for &size in SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter_batched(
+                || untagged_zones(size),
+                |from| {
+                    let mut context = DefaultContext::default();
+                    Vec::<Zone>::convert(from, &mut context, &NoArgs).unwrap()
+                },
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("declare_untagged");
+    for &size in SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter_batched(
+                || untagged_entries(size),
+                |from| {
+                    let mut context = DefaultContext::default();
+                    Vec::<Entry>::convert(from, &mut context, &NoArgs).unwrap()
+                },
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("declare_tracked");
+    for &size in SIZES {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter_batched(
+                || spawns(size),
+                |from| {
+                    let mut context = DefaultContext::default();
+                    Vec::<Zone>::convert(from, &mut context, &NoArgs).unwrap()
+                },
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ids);
+criterion_main!(benches);
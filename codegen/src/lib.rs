@@ -1,7 +1,9 @@
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned as _;
 use syn::{Error, Result};
 
 mod tests;
@@ -29,6 +31,9 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
     let mut derive_list = Vec::new();
 
     let mut processable = false;
+    let mut inverse = false;
+    let mut untagged = None;
+    let mut spanned = false;
 
     for attr in &input.attrs {
         if attr.path().is_ident("xylem") {
@@ -46,6 +51,15 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
                     InputAttr::Process => {
                         processable = true;
                     }
+                    InputAttr::Inverse => {
+                        inverse = true;
+                    }
+                    InputAttr::Untagged(span) => {
+                        untagged = Some(span);
+                    }
+                    InputAttr::Spanned => {
+                        spanned = true;
+                    }
                 }
             }
         }
@@ -92,24 +106,33 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
         #(#input_serde)*
     };
 
-    let (from_decl, convert_expr) = match &input.data {
+    if let (Some(span), false) = (untagged, matches!(input.data, syn::Data::Enum(_))) {
+        return Err(Error::new(span, "`untagged` can only be used on enums"));
+    }
+
+    let (from_decl, convert_expr, unconvert_expr) = match &input.data {
         syn::Data::Struct(data) => {
             let mut field_froms = Vec::new();
             let mut field_convs = Vec::new();
 
             for (field_ord, field) in data.fields.iter().enumerate() {
+                let member = match &field.ident {
+                    Some(field_ident) => quote!(#field_ident),
+                    None => proc_macro2::Literal::usize_unsuffixed(field_ord).to_token_stream(),
+                };
+                let field_name = field_name(field, field_ord);
                 let (from, conv) = process_field(
                     field,
-                    match &field.ident {
-                        Some(field_ident) => quote!(__xylem_from.#field_ident),
-                        None => {
-                            let field_ord = proc_macro2::Literal::usize_unsuffixed(field_ord);
-                            quote!(__xylem_from.#field_ord)
-                        }
-                    },
+                    quote!(__xylem_from.#member),
+                    quote!(self.#member),
+                    quote!(::xylem::PathSegment::Field(#field_name)),
                     &schema,
+                    spanned,
                 )?;
                 if let Some(from) = from {
+                    if inverse && from.inverse.is_none() {
+                        return Err(inverse_required_error(field));
+                    }
                     field_froms.push(from);
                 }
                 field_convs.push(conv);
@@ -119,7 +142,8 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
             let field_froms_ident: Vec<_> = field_froms.iter().map(|ff| &ff.ident).collect();
             let field_froms_ty: Vec<_> = field_froms.iter().map(|ff| &ff.ty).collect();
             let field_convs_ident: Vec<_> = field_convs.iter().map(|fc| &fc.ident).collect();
-            let field_convs_expr: Vec<_> = field_convs.iter().map(|fc| &fc.expr).collect();
+            let (field_convs_stmts, field_convs_value) = collect_fields(&field_convs);
+            let field_froms_inverse: Vec<_> = field_froms.iter().map(|ff| &ff.inverse).collect();
 
             match &data.fields {
                 syn::Fields::Named(_) => (
@@ -132,10 +156,18 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
                             )*
                         }
                     },
-                    quote! {
+                    quote! {{
+                        #field_convs_stmts
                         Self {
                             #(
-                                #field_convs_ident: #field_convs_expr,
+                                #field_convs_ident: #field_convs_value,
+                            )*
+                        }
+                    }},
+                    quote! {
+                        #from_ident {
+                            #(
+                                #field_froms_ident: #field_froms_inverse,
                             )*
                         }
                     },
@@ -147,9 +179,15 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
                             #(#field_froms_attrs #field_froms_ty,)*
                         ) #generics_where;
                     },
-                    quote! {
+                    quote! {{
+                        #field_convs_stmts
                         Self (
-                            #(#field_convs_expr,)*
+                            #(#field_convs_value,)*
+                        )
+                    }},
+                    quote! {
+                        #from_ident (
+                            #(#field_froms_inverse,)*
                         )
                     },
                 ),
@@ -161,28 +199,61 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
                     quote! {
                         Self
                     },
+                    quote! {
+                        #from_ident
+                    },
                 ),
             }
         }
+        syn::Data::Enum(data) if untagged.is_some() => {
+            let (from_field, convert_expr, unconvert_expr) =
+                untagged_enum(input_ident, data, &from_ident, &schema, inverse, spanned)?;
+            (
+                quote! {
+                    #prefix
+                    #vis struct #from_ident #generics_decl (#from_field) #generics_where;
+                },
+                convert_expr,
+                unconvert_expr,
+            )
+        }
         syn::Data::Enum(data) => {
             let mut variant_froms = Vec::new();
             let mut variant_matches = Vec::new();
+            let mut variant_unmatches = Vec::new();
 
             for variant in &data.variants {
                 let mut field_froms = Vec::new();
                 let mut field_convs = Vec::new();
 
+                let mut field_pats = Vec::new();
+
                 for (field_ord, field) in variant.fields.iter().enumerate() {
+                    let binding = match &field.ident {
+                        Some(ident) => quote!(#ident),
+                        None => format_ident!("__field{}", field_ord).to_token_stream(),
+                    };
+                    let variant_name = variant.ident.unraw().to_string();
+                    let field_name = field_name(field, field_ord);
                     let (from, conv) = process_field(
                         field,
-                        match &field.ident {
-                            Some(ident) => quote!(#ident),
-                            None => format_ident!("__field{}", field_ord).to_token_stream(),
-                        },
+                        binding.clone(),
+                        binding.clone(),
+                        quote!(::xylem::PathSegment::VariantField(#variant_name, #field_name)),
                         &schema,
+                        spanned,
                     )?;
                     if let Some(from) = from {
+                        if inverse && from.inverse.is_none() {
+                            return Err(inverse_required_error(field));
+                        }
                         field_froms.push(from);
+                        field_pats.push(binding);
+                    } else {
+                        field_pats.push(match &field.ident {
+                            Some(ident) => quote!(#ident: _),
+                            None => quote!(_),
+                        });
                     }
                     field_convs.push(conv);
                 }
@@ -231,22 +302,43 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
                 let variant_to_ident = &variant.ident;
 
                 let field_convs_ident: Vec<_> = field_convs.iter().map(|fc| &fc.ident).collect();
-                let field_convs_expr: Vec<_> = field_convs.iter().map(|fc| &fc.expr).collect();
+                let (field_convs_stmts, field_convs_value) = collect_fields(&field_convs);
                 let variant_to_fields_expr = match &variant.fields {
                     syn::Fields::Named(_) => {
-                        quote!({ #(#field_convs_ident: #field_convs_expr),* })
+                        quote!({ #(#field_convs_ident: #field_convs_value),* })
                     }
                     syn::Fields::Unnamed(_) => {
-                        quote!((#(#field_convs_expr),*))
+                        quote!((#(#field_convs_value),*))
                     }
                     syn::Fields::Unit => quote!(),
                 };
 
                 let variant_match = quote! {
-                    #from_ident::#variant_from_ident #variant_from_fields_pat =>
+                    #from_ident::#variant_from_ident #variant_from_fields_pat => {
+                        #field_convs_stmts
                         Self::#variant_to_ident #variant_to_fields_expr
+                    }
                 };
                 variant_matches.push(variant_match);
+
+                let variant_to_fields_pat = match &variant.fields {
+                    syn::Fields::Named(_) => quote!({ #(#field_pats),* }),
+                    syn::Fields::Unnamed(_) => quote!((#(#field_pats),*)),
+                    syn::Fields::Unit => quote!(),
+                };
+                let field_froms_inverse: Vec<_> =
+                    field_froms.iter().map(|ff| &ff.inverse).collect();
+                let variant_from_fields_expr = match &variant.fields {
+                    syn::Fields::Named(_) => {
+                        quote!({ #(#field_froms_ident: #field_froms_inverse),* })
+                    }
+                    syn::Fields::Unnamed(_) => quote!((#(#field_froms_inverse),*)),
+                    syn::Fields::Unit => quote!(),
+                };
+                variant_unmatches.push(quote! {
+                    Self::#variant_to_ident #variant_to_fields_pat =>
+                        #from_ident::#variant_from_ident #variant_from_fields_expr
+                });
             }
 
             (
@@ -261,6 +353,11 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
                         #(#variant_matches),*
                     }
                 },
+                quote! {
+                    match self {
+                        #(#variant_unmatches),*
+                    }
+                },
             )
         }
         syn::Data::Union(data) => {
@@ -287,12 +384,200 @@ fn xylem_impl(ts: TokenStream) -> Result<Output> {
             }
         }
     };
-    Ok(Output { from_decl, xylem_impl, expose_from_type })
+    let inverse_impl = inverse.then(|| {
+        quote! {
+            #[automatically_derived]
+            #[allow(clippy::needless_update)]
+            impl #generics_decl ::xylem::XylemInverse<#schema> for #input_ident #generics_usage {
+                fn unconvert_impl(
+                    self,
+                    __xylem_context: &mut <#schema as ::xylem::Schema>::Context,
+                    _: &Self::Args,
+                ) -> Result<Self::From, <#schema as ::xylem::Schema>::Error> {
+                    Ok(#unconvert_expr)
+                }
+            }
+        }
+    });
+
+    Ok(Output { from_decl, xylem_impl, inverse_impl, expose_from_type })
+}
+
+/// Generates the conversions for an enum with `#[xylem(untagged)]`.
+///
+/// Returns the field of the `From` newtype,
+/// the conversion expression and the inverse conversion expression.
+fn untagged_enum(
+    input_ident: &syn::Ident,
+    data: &syn::DataEnum,
+    from_ident: &syn::Ident,
+    schema: &syn::Type,
+    inverse: bool,
+    spanned: bool,
+) -> Result<(TokenStream, TokenStream, TokenStream)> {
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            input_ident,
+            "Untagged enums must have at least one variant",
+        ));
+    }
+
+    let mut from_field = None;
+    let mut attempts = Vec::new();
+    let mut variant_unmatches = Vec::new();
+
+    for (variant_ord, variant) in data.variants.iter().enumerate() {
+        let field = match variant.fields.iter().collect::<Vec<_>>()[..] {
+            [field] => field,
+            _ => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "Variants of untagged enums must have exactly one field",
+                ))
+            }
+        };
+
+        // Only the last attempt can take the value; the previous attempts convert a clone.
+        let from_expr = if variant_ord + 1 == data.variants.len() {
+            quote!(__xylem_from.0)
+        } else {
+            quote!(::std::clone::Clone::clone(&__xylem_from.0))
+        };
+        let binding = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => quote!(__field0),
+        };
+        let variant_name = variant.ident.unraw().to_string();
+        let field_name = field_name(field, 0);
+        let (from, conv) = process_field(
+            field,
+            from_expr,
+            binding.clone(),
+            quote!(::xylem::PathSegment::VariantField(#variant_name, #field_name)),
+            schema,
+            spanned,
+        )?;
+        let from = match from {
+            Some(from) => from,
+            None => {
+                return Err(Error::new_spanned(
+                    field,
+                    "Fields of untagged enum variants cannot use `default`",
+                ))
+            }
+        };
+        if inverse && from.inverse.is_none() {
+            return Err(inverse_required_error(field));
+        }
+
+        let variant_ident = &variant.ident;
+        let (variant_pat, variant_expr) = match &field.ident {
+            Some(ident) => (quote!({ #ident }), quote!({ #ident: __xylem_value })),
+            None => (quote!((__field0)), quote!((__xylem_value))),
+        };
+
+        // The derived type only has the field of the first variant,
+        // so the other variants must have the same derived field type.
+        // This is asserted on the field to report mismatches on the offending variant.
+        let type_check = match &from_field {
+            Some((_, first_ty)) => {
+                let ty = &from.ty;
+                quote_spanned!(field.span()=> ::xylem::assert_untagged_from::<#ty, #first_ty>();)
+            }
+            None => quote!(),
+        };
+
+        let conv_expr = &conv.expr;
+        let merge = if variant_ord == 0 {
+            quote!(__xylem_variant_error)
+        } else {
+            quote!(::xylem::AbstractError::merge(__xylem_error, __xylem_variant_error))
+        };
+        attempts.push(quote! {
+            #type_check
+            let __xylem_error = match ::xylem::Context::transaction(
+                __xylem_context,
+                |__xylem_context| #conv_expr,
+            ) {
+                Ok(__xylem_value) => break '__xylem_untagged Self::#variant_ident #variant_expr,
+                Err(__xylem_variant_error) => #merge,
+            };
+        });
+
+        let field_inverse = &from.inverse;
+        variant_unmatches.push(quote! {
+            Self::#variant_ident #variant_pat => #from_ident(#field_inverse)
+        });
+
+        if from_field.is_none() {
+            from_field = Some((from.attrs.clone(), from.ty.clone()));
+        }
+    }
+
+    let (attrs, ty) = from_field.expect("checked non-empty variants");
+    Ok((
+        quote!(#attrs #ty),
+        quote! {
+            '__xylem_untagged: {
+                #(#attempts)*
+                return Err(__xylem_error);
+            }
+        },
+        quote! {
+            match self {
+                #(#variant_unmatches),*
+            }
+        },
+    ))
+}
+
+/// Generates the statements to convert each field through an `ErrorCollector`,
+/// and the expressions to retrieve the converted values after the statements.
+fn collect_fields(field_convs: &[FieldConv]) -> (TokenStream, Vec<TokenStream>) {
+    if field_convs.is_empty() {
+        return (TokenStream::new(), Vec::new());
+    }
+
+    let vars: Vec<_> = (0..field_convs.len())
+        .map(|field_ord| format_ident!("__xylem_field{}", field_ord))
+        .collect();
+    let exprs = field_convs.iter().map(|fc| &fc.expr);
+
+    let stmts = quote! {
+        let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+        #(
+            let #vars = __xylem_errors.collect(#exprs)?;
+        )*
+        __xylem_errors.finish()?;
+    };
+    let values = vars
+        .iter()
+        .map(|var| quote!(#var.expect("all fields are converted if no errors are collected")))
+        .collect();
+
+    (stmts, values)
+}
+
+/// Returns the name of the field in `PathSegment`s.
+fn field_name(field: &syn::Field, field_ord: usize) -> String {
+    match &field.ident {
+        Some(ident) => ident.unraw().to_string(),
+        None => field_ord.to_string(),
+    }
+}
+
+fn inverse_required_error(field: &syn::Field) -> Error {
+    Error::new_spanned(
+        field,
+        "`transform` and `transform_with_context` fields must specify `inverse` to derive \
+         `XylemInverse`",
+    )
 }
 
 struct Output {
     from_decl:        TokenStream,
     xylem_impl:       TokenStream,
+    inverse_impl:     Option<TokenStream>,
     expose_from_type: bool,
 }
 
@@ -300,10 +585,12 @@ impl Output {
     fn output(&self) -> TokenStream {
         let from_decl = &self.from_decl;
         let xylem_impl = &self.xylem_impl;
+        let inverse_impl = &self.inverse_impl;
 
         let inner = quote! {
             #from_decl
             #xylem_impl
+            #inverse_impl
         };
 
         if self.expose_from_type {
@@ -331,6 +618,12 @@ enum InputAttr {
     Derive(Punctuated<syn::Path, syn::Token![,]>),
     /// Call [`Processable`].
     Process,
+    /// Derive `XylemInverse`.
+    Inverse,
+    /// Convert enums by trying each variant in order.
+    Untagged(Span),
+    /// Wrap the standard fields of the `From` type in `Spanned`.
+    Spanned,
 }
 
 impl Parse for InputAttr {
@@ -353,6 +646,12 @@ impl Parse for InputAttr {
             Ok(Self::Derive(Punctuated::parse_terminated(&inner)?))
         } else if ident == "process" {
             Ok(Self::Process)
+        } else if ident == "inverse" {
+            Ok(Self::Inverse)
+        } else if ident == "untagged" {
+            Ok(Self::Untagged(ident.span()))
+        } else if ident == "spanned" {
+            Ok(Self::Spanned)
         } else {
             Err(Error::new_spanned(ident, "Unsupported attribute"))
         }
@@ -391,6 +690,11 @@ enum FieldAttr {
     Default(syn::Expr),
     /// Pass arguments to the field type.
     Args(Span, Punctuated<ArgDef, syn::Token![,]>),
+    /// The inverse of [`FieldAttr::Transform`] or [`FieldAttr::TransformWithContext`].
+    ///
+    /// The signature is `fn(Bar) -> Result<Type, S::Error>`,
+    /// with an extra `&mut S::Context` parameter for [`FieldAttr::TransformWithContext`].
+    Inverse(syn::Path),
 }
 
 impl Parse for FieldAttr {
@@ -424,6 +728,9 @@ impl Parse for FieldAttr {
             let inner;
             syn::parenthesized!(inner in input);
             Ok(Self::Args(ident.span(), Punctuated::parse_terminated(&inner)?))
+        } else if ident == "inverse" {
+            let _: syn::Token![=] = input.parse()?;
+            Ok(Self::Inverse(input.parse()?))
         } else {
             Err(Error::new_spanned(ident, "Unsupported attribute"))
         }
@@ -447,7 +754,10 @@ impl Parse for ArgDef {
 fn process_field(
     field: &syn::Field,
     from_expr: TokenStream,
+    to_expr: TokenStream,
+    segment: TokenStream,
     schema: &syn::Type,
+    spanned: bool,
 ) -> Result<(Option<FieldFrom>, FieldConv)> {
     enum Mode {
         Standard(Vec<ArgDef>),
@@ -456,6 +766,7 @@ fn process_field(
     }
 
     let mut mode = Mode::Standard(Vec::new());
+    let mut inverse = None;
 
     let mut from_attrs = TokenStream::new();
 
@@ -480,6 +791,7 @@ fn process_field(
                             ty:      Box::new(field.ty.clone()),
                             context: false,
                         };
+                        inverse = Some(quote!(Ok));
                     }
                     FieldAttr::Transform(path, ty) => {
                         if !matches!(mode, Mode::Standard(_)) {
@@ -516,9 +828,12 @@ fn process_field(
                         }
                         mode = Mode::Default(quote!(#expr));
                     }
+                    FieldAttr::Inverse(path) => {
+                        inverse = Some(quote!(#path));
+                    }
                     FieldAttr::Args(span, args) => match &mut mode {
                         Mode::Standard(arg_defs) => {
-                            arg_defs.extend(args.into_iter());
+                            arg_defs.extend(args);
                         }
                         _ => {
                             return Err(Error::new(
@@ -533,53 +848,101 @@ fn process_field(
         }
     }
 
+    if let (Some(inverse), Mode::Standard(_) | Mode::Default(_)) = (&inverse, &mode) {
+        return Err(Error::new_spanned(
+            inverse,
+            "`inverse` can only be used with `transform` or `transform_with_context`.",
+        ));
+    }
+
     Ok(match mode {
-        Mode::Standard(arg_defs) => (
-            Some(FieldFrom {
-                attrs: from_attrs,
-                ident: field.ident.clone(),
-                ty:    {
-                    let ty = &field.ty;
-                    quote!(<#ty as ::xylem::Xylem<#schema>>::From)
-                },
-            }),
-            FieldConv {
-                ident: field.ident.clone(),
-                expr:  {
-                    let ty = &field.ty;
-                    let arg_names = arg_defs.iter().map(|def| &def.name);
-                    let arg_exprs = arg_defs.iter().map(|def| &def.expr);
+        Mode::Standard(arg_defs) => {
+            let ty = &field.ty;
+            let arg_names: Vec<_> = arg_defs.iter().map(|def| &def.name).collect();
+            let arg_exprs: Vec<_> = arg_defs.iter().map(|def| &def.expr).collect();
+            let args = quote! {
+                type Args = <#ty as ::xylem::Xylem<#schema>>::Args;
+                ::xylem::lazy_static! {
+                    static ref __XYLEM_ARGS: Args = Args {
+                        #(#arg_names: #arg_exprs,)*
+                        ..::std::default::Default::default()
+                    };
+                }
+            };
+
+            // `spanned` fields are converted through `Spanned<Field>`.
+            // The span of the original value is unknown in the inverse conversion.
+            let (from_ty, convert, map_value, to_value) = if spanned {
+                (
+                    quote!(<::xylem::Spanned<#ty> as ::xylem::Xylem<#schema>>::From),
+                    quote!(<::xylem::Spanned<#ty> as ::xylem::Xylem<#schema>>::convert),
+                    quote!(.map(::xylem::Spanned::into_value)),
+                    quote!(::xylem::Spanned::new(#to_expr, 0..0)),
+                )
+            } else {
+                (
+                    quote!(<#ty as ::xylem::Xylem<#schema>>::From),
+                    quote!(::xylem::Xylem::<#schema>::convert),
+                    quote!(),
+                    to_expr,
+                )
+            };
 
-                    quote! {{
-                        type Args = <#ty as ::xylem::Xylem<#schema>>::Args;
-                        ::xylem::lazy_static! {
-                            static ref __XYLEM_ARGS: Args = Args {
-                                #(#arg_names: #arg_exprs,)*
-                                ..::std::default::Default::default()
-                            };
-                        }
-                        ::xylem::Xylem::<#schema>::convert(
-                            #from_expr,
+            (
+                Some(FieldFrom {
+                    attrs:   from_attrs,
+                    ident:   field.ident.clone(),
+                    ty:      from_ty,
+                    inverse: Some(quote! {{
+                        #args
+                        ::xylem::Context::set_next_segment(__xylem_context, #segment);
+                        ::xylem::XylemInverse::<#schema>::unconvert(
+                            #to_value,
                             __xylem_context,
                             &*__XYLEM_ARGS,
                         )?
-                    }}
+                    }}),
+                }),
+                FieldConv {
+                    ident: field.ident.clone(),
+                    expr:  quote! {{
+                        #args
+                        ::xylem::Context::set_next_segment(__xylem_context, #segment);
+                        #convert(
+                            #from_expr,
+                            __xylem_context,
+                            &*__XYLEM_ARGS,
+                        )
+                        #map_value
+                    }},
                 },
-            },
-        ),
-        Mode::Default(expr) => (None, FieldConv { ident: field.ident.clone(), expr }),
+            )
+        }
+        Mode::Default(expr) => {
+            (None, FieldConv { ident: field.ident.clone(), expr: quote!(Ok(#expr)) })
+        }
         Mode::Transform { ts, ty, context } => {
             let context = context.then(|| quote!(__xylem_context));
             (
                 Some(FieldFrom {
-                    attrs: from_attrs,
-                    ident: field.ident.clone(),
-                    ty:    quote!(#ty),
+                    attrs:   from_attrs,
+                    ident:   field.ident.clone(),
+                    ty:      quote!(#ty),
+                    inverse: inverse.map(|inverse| {
+                        quote! {
+                            #inverse(#to_expr, #context)?
+                        }
+                    }),
                 }),
                 FieldConv {
                     ident: field.ident.clone(),
+                    // no scope is started for the transformed value,
+                    // so the path is annotated here
                     expr:  quote! {
-                        #ts(#from_expr, #context)?
+                        #ts(#from_expr, #context).map_err(|err| {
+                            let path = ::xylem::Context::path(&*__xylem_context).child(#segment);
+                            <<#schema as ::xylem::Schema>::Error as ::xylem::AbstractError>::with_path(err, path)
+                        })
                     },
                 },
             )
@@ -590,17 +953,21 @@ fn process_field(
 #[derive(Debug)]
 struct FieldFrom {
     /// The attributes of the field in the `From` type.
-    attrs: TokenStream,
+    attrs:   TokenStream,
     /// The name of the field in the `From` type.
-    ident: Option<syn::Ident>,
+    ident:   Option<syn::Ident>,
     /// The type of the field in the `From` type.
-    ty:    TokenStream,
+    ty:      TokenStream,
+    /// The expression of the field in the inverse conversion,
+    /// or `None` if the field cannot be converted back.
+    inverse: Option<TokenStream>,
 }
 
 #[derive(Debug)]
 struct FieldConv {
     /// The name of the field in the `Self` type.
     ident: Option<syn::Ident>,
-    /// The expression of the field in the constructor.
+    /// The expression that converts the field,
+    /// evaluating to `Result<Field, S::Error>`.
     expr:  TokenStream,
 }
@@ -22,6 +22,18 @@ fn test_ok(input: TokenStream, expect_from_decl: TokenStream, expect_xylem_impl:
     );
 }
 
+fn test_inverse_ok(input: TokenStream, expect_inverse_impl: TokenStream) {
+    let output = xylem_impl(input).expect("Proc macro returned with compile error");
+    let inverse_impl = output.inverse_impl.expect("Inverse impl was not generated");
+
+    assert!(
+        token_stream_equals(expect_inverse_impl.clone(), inverse_impl.clone()),
+        "Expected `XylemInverse` impl:\n{}\n, actual `XylemInverse` impl:\n{}\n",
+        &expect_inverse_impl,
+        &inverse_impl
+    );
+}
+
 #[test]
 fn test_named_struct() {
     test_ok(
@@ -50,21 +62,29 @@ fn test_named_struct() {
                     __xylem_context: &mut <crate::Schema as ::xylem::Schema>::Context,
                     _: &Self::Args,
                 ) -> Result<Self, <crate::Schema as ::xylem::Schema>::Error> {
-                    let mut __xylem_ret = Self {
-                        bar: {
+                    let mut __xylem_ret = {
+                        let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+                        let __xylem_field0 = __xylem_errors.collect({
                             type Args = <Bar as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.bar, __xylem_context, &*__XYLEM_ARGS)?
-                        },
-                        qux: {
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("bar"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.bar, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        let __xylem_field1 = __xylem_errors.collect({
                             type Args = <Qux as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.qux, __xylem_context, &*__XYLEM_ARGS)?
-                        },
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("qux"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.qux, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        __xylem_errors.finish()?;
+                        Self {
+                            bar: __xylem_field0.expect("all fields are converted if no errors are collected"),
+                            qux: __xylem_field1.expect("all fields are converted if no errors are collected"),
+                        }
                     };
                     Ok(__xylem_ret)
                 }
@@ -98,22 +118,30 @@ fn test_tuple_struct() {
                     __xylem_context: &mut <crate::Schema as ::xylem::Schema>::Context,
                     _: &Self::Args,
                 ) -> Result<Self, <crate::Schema as ::xylem::Schema>::Error> {
-                    let mut __xylem_ret = Self (
-                        {
+                    let mut __xylem_ret = {
+                        let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+                        let __xylem_field0 = __xylem_errors.collect({
                             type Args = <Bar as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.0, __xylem_context, &*__XYLEM_ARGS)?
-                        },
-                        {
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("0"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.0, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        let __xylem_field1 = __xylem_errors.collect({
                             type Args = <Qux as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.1, __xylem_context, &*__XYLEM_ARGS)?
-                        },
-                    );
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("1"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.1, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        __xylem_errors.finish()?;
+                        Self(
+                            __xylem_field0.expect("all fields are converted if no errors are collected"),
+                            __xylem_field1.expect("all fields are converted if no errors are collected"),
+                        )
+                    };
                     Ok(__xylem_ret)
                 }
             }
@@ -179,21 +207,29 @@ fn test_generic_named_struct() {
                     __xylem_context: &mut <crate::Schema as ::xylem::Schema>::Context,
                     _: &Self::Args,
                 ) -> Result<Self, <crate::Schema as ::xylem::Schema>::Error> {
-                    let mut __xylem_ret = Self {
-                        bar: {
+                    let mut __xylem_ret = {
+                        let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+                        let __xylem_field0 = __xylem_errors.collect({
                             type Args = <Bar<T> as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.bar, __xylem_context, &*__XYLEM_ARGS)?
-                        },
-                        qux: {
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("bar"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.bar, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        let __xylem_field1 = __xylem_errors.collect({
                             type Args = <Qux<U> as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.qux, __xylem_context, &*__XYLEM_ARGS)?
-                        },
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("qux"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.qux, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        __xylem_errors.finish()?;
+                        Self {
+                            bar: __xylem_field0.expect("all fields are converted if no errors are collected"),
+                            qux: __xylem_field1.expect("all fields are converted if no errors are collected"),
+                        }
                     };
                     Ok(__xylem_ret)
                 }
@@ -227,22 +263,30 @@ fn test_generic_tuple_struct() {
                     __xylem_context: &mut <crate::Schema as ::xylem::Schema>::Context,
                     _: &Self::Args,
                 ) -> Result<Self, <crate::Schema as ::xylem::Schema>::Error> {
-                    let mut __xylem_ret = Self (
-                        {
+                    let mut __xylem_ret = {
+                        let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+                        let __xylem_field0 = __xylem_errors.collect({
                             type Args = <Bar<T> as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.0, __xylem_context, &*__XYLEM_ARGS)?
-                        },
-                        {
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("0"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.0, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        let __xylem_field1 = __xylem_errors.collect({
                             type Args = <Qux<U> as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.1, __xylem_context, &*__XYLEM_ARGS)?
-                        },
-                    );
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("1"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.1, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        __xylem_errors.finish()?;
+                        Self(
+                            __xylem_field0.expect("all fields are converted if no errors are collected"),
+                            __xylem_field1.expect("all fields are converted if no errors are collected"),
+                        )
+                    };
                     Ok(__xylem_ret)
                 }
             }
@@ -293,38 +337,54 @@ fn test_enum() {
                     _: &Self::Args,
                 ) -> Result<Self, <crate::Schema as ::xylem::Schema>::Error> {
                     let mut __xylem_ret = match __xylem_from {
-                        FooXylem::Bar => Self::Bar,
-                        FooXylem::Qux(__field0, __field1) => Self::Qux(
-                            {
+                        FooXylem::Bar => { Self::Bar },
+                        FooXylem::Qux(__field0, __field1) => {
+                            let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+                            let __xylem_field0 = __xylem_errors.collect({
                                 type Args = <Corge as ::xylem::Xylem<crate::Schema>>::Args;
                                 ::xylem::lazy_static! {
                                     static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                                 }
-                                ::xylem::Xylem::<crate::Schema>::convert(__field0, __xylem_context, &*__XYLEM_ARGS)?
-                            },
-                            {
+                                ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::VariantField("Qux", "0"));
+                                ::xylem::Xylem::<crate::Schema>::convert(__field0, __xylem_context, &*__XYLEM_ARGS)
+                            })?;
+                            let __xylem_field1 = __xylem_errors.collect({
                                 type Args = <Quz as ::xylem::Xylem<crate::Schema>>::Args;
                                 ::xylem::lazy_static! {
                                     static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                                 }
-                                ::xylem::Xylem::<crate::Schema>::convert(__field1, __xylem_context, &*__XYLEM_ARGS)?
-                            },
-                        ),
-                        FooXylem::Grault { waldo, fred } => Self::Grault {
-                            waldo: {
+                                ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::VariantField("Qux", "1"));
+                                ::xylem::Xylem::<crate::Schema>::convert(__field1, __xylem_context, &*__XYLEM_ARGS)
+                            })?;
+                            __xylem_errors.finish()?;
+                            Self::Qux(
+                                __xylem_field0.expect("all fields are converted if no errors are collected"),
+                                __xylem_field1.expect("all fields are converted if no errors are collected"),
+                            )
+                        },
+                        FooXylem::Grault { waldo, fred } => {
+                            let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+                            let __xylem_field0 = __xylem_errors.collect({
                                 type Args = <Waldo as ::xylem::Xylem<crate::Schema>>::Args;
                                 ::xylem::lazy_static! {
                                     static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                                 }
-                                ::xylem::Xylem::<crate::Schema>::convert(waldo, __xylem_context, &*__XYLEM_ARGS)?
-                            },
-                            fred: {
+                                ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::VariantField("Grault", "waldo"));
+                                ::xylem::Xylem::<crate::Schema>::convert(waldo, __xylem_context, &*__XYLEM_ARGS)
+                            })?;
+                            let __xylem_field1 = __xylem_errors.collect({
                                 type Args = <Fred as ::xylem::Xylem<crate::Schema>>::Args;
                                 ::xylem::lazy_static! {
                                     static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                                 }
-                                ::xylem::Xylem::<crate::Schema>::convert(fred, __xylem_context, &*__XYLEM_ARGS)?
-                            },
+                                ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::VariantField("Grault", "fred"));
+                                ::xylem::Xylem::<crate::Schema>::convert(fred, __xylem_context, &*__XYLEM_ARGS)
+                            })?;
+                            __xylem_errors.finish()?;
+                            Self::Grault {
+                                waldo: __xylem_field0.expect("all fields are converted if no errors are collected"),
+                                fred: __xylem_field1.expect("all fields are converted if no errors are collected"),
+                            }
                         },
                     };
                     Ok(__xylem_ret)
@@ -364,21 +424,29 @@ fn test_processable() {
                     _: &Self::Args,
                 ) -> Result<Self, <crate::Schema as ::xylem::Schema>::Error> {
                     <Self as ::xylem::Processable<crate::Schema>>::preprocess(&mut __xylem_from, __xylem_context)?;
-                    let mut __xylem_ret = Self {
-                        bar: {
+                    let mut __xylem_ret = {
+                        let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+                        let __xylem_field0 = __xylem_errors.collect({
                             type Args = <Bar as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.bar, __xylem_context, &*__XYLEM_ARGS)?
-                        },
-                        qux: {
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("bar"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.bar, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        let __xylem_field1 = __xylem_errors.collect({
                             type Args = <Qux as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.qux, __xylem_context, &*__XYLEM_ARGS)?
-                        },
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("qux"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.qux, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        __xylem_errors.finish()?;
+                        Self {
+                            bar: __xylem_field0.expect("all fields are converted if no errors are collected"),
+                            qux: __xylem_field1.expect("all fields are converted if no errors are collected"),
+                        }
                     };
                     <Self as ::xylem::Processable<crate::Schema>>::postprocess(&mut __xylem_ret, __xylem_context)?;
                     Ok(__xylem_ret)
@@ -421,21 +489,182 @@ fn test_attrs() {
                     __xylem_context: &mut <crate::Schema as ::xylem::Schema>::Context,
                     _: &Self::Args,
                 ) -> Result<Self, <crate::Schema as ::xylem::Schema>::Error> {
-                    let mut __xylem_ret = Self {
-                        bar: {
+                    let mut __xylem_ret = {
+                        let mut __xylem_errors = ::xylem::ErrorCollector::new(&*__xylem_context);
+                        let __xylem_field0 = __xylem_errors.collect({
                             type Args = <Bar as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.bar, __xylem_context, &*__XYLEM_ARGS)?
-                        },
-                        qux: {
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("bar"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.bar, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        let __xylem_field1 = __xylem_errors.collect({
                             type Args = <Qux as ::xylem::Xylem<crate::Schema>>::Args;
                             ::xylem::lazy_static! {
                                 static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                             }
-                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.qux, __xylem_context, &*__XYLEM_ARGS)?
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("qux"));
+                            ::xylem::Xylem::<crate::Schema>::convert(__xylem_from.qux, __xylem_context, &*__XYLEM_ARGS)
+                        })?;
+                        __xylem_errors.finish()?;
+                        Self {
+                            bar: __xylem_field0.expect("all fields are converted if no errors are collected"),
+                            qux: __xylem_field1.expect("all fields are converted if no errors are collected"),
+                        }
+                    };
+                    Ok(__xylem_ret)
+                }
+            }
+        },
+    );
+}
+
+#[test]
+fn test_inverse_struct() {
+    test_inverse_ok(
+        quote! {
+            #[xylem(inverse)]
+            struct Foo {
+                bar: Bar,
+                #[xylem(default = Ok(Qux))]
+                qux: Qux,
+            }
+        },
+        quote! {
+            #[automatically_derived]
+            #[allow(clippy::needless_update)]
+            impl ::xylem::XylemInverse<crate::Schema> for Foo {
+                fn unconvert_impl(
+                    self,
+                    __xylem_context: &mut <crate::Schema as ::xylem::Schema>::Context,
+                    _: &Self::Args,
+                ) -> Result<Self::From, <crate::Schema as ::xylem::Schema>::Error> {
+                    Ok(FooXylem {
+                        bar: {
+                            type Args = <Bar as ::xylem::Xylem<crate::Schema>>::Args;
+                            ::xylem::lazy_static! {
+                                static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
+                            }
+                            ::xylem::Context::set_next_segment(__xylem_context, ::xylem::PathSegment::Field("bar"));
+                            ::xylem::XylemInverse::<crate::Schema>::unconvert(self.bar, __xylem_context, &*__XYLEM_ARGS)?
                         },
+                    })
+                }
+            }
+        },
+    );
+}
+
+#[test]
+fn test_inverse_enum() {
+    test_inverse_ok(
+        quote! {
+            #[xylem(inverse)]
+            enum Foo {
+                Bar,
+                Qux(#[xylem(default = Ok(Corge))] Corge, #[xylem(preserve)] Quz),
+            }
+        },
+        quote! {
+            #[automatically_derived]
+            #[allow(clippy::needless_update)]
+            impl ::xylem::XylemInverse<crate::Schema> for Foo {
+                fn unconvert_impl(
+                    self,
+                    __xylem_context: &mut <crate::Schema as ::xylem::Schema>::Context,
+                    _: &Self::Args,
+                ) -> Result<Self::From, <crate::Schema as ::xylem::Schema>::Error> {
+                    Ok(match self {
+                        Self::Bar => FooXylem::Bar,
+                        Self::Qux(_, __field1) => FooXylem::Qux(Ok(__field1)?),
+                    })
+                }
+            }
+        },
+    );
+}
+
+#[test]
+fn test_inverse_requires_transform_inverse() {
+    let result = xylem_impl(quote! {
+        #[xylem(inverse)]
+        struct Foo {
+            #[xylem(transform = bar(Bar))]
+            bar: Qux,
+        }
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_untagged_enum() {
+    test_ok(
+        quote! {
+            #[xylem(untagged)]
+            enum Foo {
+                Bar(Bar),
+                Qux { #[xylem(transform = qux(Corge))] qux: Qux },
+            }
+        },
+        quote! {
+            #[doc = concat!("See [`", stringify!(FooXylem), "`]")]
+            #[automatically_derived]
+            struct FooXylem(<Bar as ::xylem::Xylem<crate::Schema>>::From);
+        },
+        quote! {
+            #[automatically_derived]
+            #[allow(clippy::needless_update)]
+            impl ::xylem::Xylem<crate::Schema> for Foo {
+                type From = FooXylem;
+                type Args = ::xylem::NoArgs;
+
+                fn convert_impl(
+                    mut __xylem_from: Self::From,
+                    __xylem_context: &mut <crate::Schema as ::xylem::Schema>::Context,
+                    _: &Self::Args,
+                ) -> Result<Self, <crate::Schema as ::xylem::Schema>::Error> {
+                    let mut __xylem_ret = '__xylem_untagged: {
+                        let __xylem_error = match ::xylem::Context::transaction(
+                            __xylem_context,
+                            |__xylem_context| {
+                                type Args = <Bar as ::xylem::Xylem<crate::Schema>>::Args;
+                                ::xylem::lazy_static! {
+                                    static ref __XYLEM_ARGS: Args = Args {
+                                        ..::std::default::Default::default()
+                                    };
+                                }
+                                ::xylem::Context::set_next_segment(
+                                    __xylem_context,
+                                    ::xylem::PathSegment::VariantField("Bar", "0")
+                                );
+                                ::xylem::Xylem::<crate::Schema>::convert(
+                                    ::std::clone::Clone::clone(&__xylem_from.0),
+                                    __xylem_context,
+                                    &*__XYLEM_ARGS,
+                                )
+                            },
+                        ) {
+                            Ok(__xylem_value) => break '__xylem_untagged Self::Bar(__xylem_value),
+                            Err(__xylem_variant_error) => __xylem_variant_error,
+                        };
+                        ::xylem::assert_untagged_from::<Corge, <Bar as ::xylem::Xylem<crate::Schema>>::From>();
+                        let __xylem_error = match ::xylem::Context::transaction(
+                            __xylem_context,
+                            |__xylem_context| qux(__xylem_from.0,).map_err(|err| {
+                                let path = ::xylem::Context::path(&*__xylem_context)
+                                    .child(::xylem::PathSegment::VariantField("Qux", "qux"));
+                                <<crate::Schema as ::xylem::Schema>::Error
+                                    as ::xylem::AbstractError>::with_path(err, path)
+                            }),
+                        ) {
+                            Ok(__xylem_value) => break '__xylem_untagged Self::Qux { qux: __xylem_value },
+                            Err(__xylem_variant_error) => ::xylem::AbstractError::merge(
+                                __xylem_error,
+                                __xylem_variant_error,
+                            ),
+                        };
+                        return Err(__xylem_error);
                     };
                     Ok(__xylem_ret)
                 }
@@ -443,3 +672,20 @@ fn test_attrs() {
         },
     );
 }
+
+#[test]
+fn test_untagged_requires_single_field() {
+    let result = xylem_impl(quote! {
+        #[xylem(untagged)]
+        enum Foo {
+            Bar(Bar, Qux),
+        }
+    });
+    assert!(result.is_err());
+
+    let result = xylem_impl(quote! {
+        #[xylem(untagged)]
+        struct Foo(Bar);
+    });
+    assert!(result.is_err());
+}
@@ -5,6 +5,14 @@ use crate::tests::token_stream_equals;
 use crate::{process_field, FieldConv, FieldFrom};
 
 fn test_process_field(fields: TokenStream, expects: &[(Option<FieldFrom>, FieldConv)]) {
+    test_process_field_spanned(fields, false, expects)
+}
+
+fn test_process_field_spanned(
+    fields: TokenStream,
+    spanned: bool,
+    expects: &[(Option<FieldFrom>, FieldConv)],
+) {
     let full = quote! {
         struct Test #fields
     };
@@ -14,8 +22,11 @@ fn test_process_field(fields: TokenStream, expects: &[(Option<FieldFrom>, FieldC
         let (actual_from, actual_conv) = process_field(
             field,
             quote!(_from_placeholder_),
+            quote!(_to_placeholder_),
+            quote!(_segment_placeholder_),
             &syn::parse2::<syn::Type>(quote!(::_placeholder_::_Schema_))
                 .expect("Cannot parse literal token stream"),
+            spanned,
         )
         .expect("Invalid test case");
 
@@ -46,6 +57,20 @@ fn test_process_field(fields: TokenStream, expects: &[(Option<FieldFrom>, FieldC
                     &expect.ty,
                     &actual.ty,
                 );
+
+                match (&expect.inverse, &actual.inverse) {
+                    (None, None) => {}
+                    (Some(expect), Some(actual)) => assert!(
+                        token_stream_equals(expect.clone(), actual.clone()),
+                        "Expected FieldFrom.inverse =\n{}\n, actual FieldFrom.inverse =\n{}\n",
+                        expect,
+                        actual,
+                    ),
+                    (expect, actual) => panic!(
+                        "Expected FieldFrom.inverse = {:?}, actual FieldFrom.inverse = {:?}",
+                        expect, actual,
+                    ),
+                }
             }
             _ => panic!("Expected FieldFrom = {:?}, got {:?}", expect_from, actual_from),
         }
@@ -78,9 +103,21 @@ fn test_field_standard_named() {
         }),
         &[(
             Some(FieldFrom {
-                attrs: quote! {},
-                ident: Some(Ident::new("foo", Span::call_site())),
-                ty:    quote!(<Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::From),
+                attrs:   quote! {},
+                ident:   Some(Ident::new("foo", Span::call_site())),
+                ty:      quote!(<Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::From),
+                inverse: Some(quote! {{
+                    type Args = <Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::Args;
+                    ::xylem::lazy_static! {
+                        static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
+                    }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
+                    ::xylem::XylemInverse::<::_placeholder_::_Schema_>::unconvert(
+                        _to_placeholder_,
+                        __xylem_context,
+                        &*__XYLEM_ARGS,
+                    )?
+                }}),
             }),
             FieldConv {
                 ident: Some(Ident::new("foo", Span::call_site())),
@@ -89,11 +126,12 @@ fn test_field_standard_named() {
                     ::xylem::lazy_static! {
                         static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                     }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
                     ::xylem::Xylem::<::_placeholder_::_Schema_>::convert(
                         _from_placeholder_,
                         __xylem_context,
                         &*__XYLEM_ARGS,
-                    )?
+                    )
                 }},
             },
         )],
@@ -106,9 +144,21 @@ fn test_field_standard_unnamed() {
         quote!((Bar);),
         &[(
             Some(FieldFrom {
-                attrs: quote! {},
-                ident: None,
-                ty:    quote!(<Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::From),
+                attrs:   quote! {},
+                ident:   None,
+                ty:      quote!(<Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::From),
+                inverse: Some(quote! {{
+                    type Args = <Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::Args;
+                    ::xylem::lazy_static! {
+                        static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
+                    }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
+                    ::xylem::XylemInverse::<::_placeholder_::_Schema_>::unconvert(
+                        _to_placeholder_,
+                        __xylem_context,
+                        &*__XYLEM_ARGS,
+                    )?
+                }}),
             }),
             FieldConv {
                 ident: None,
@@ -117,11 +167,12 @@ fn test_field_standard_unnamed() {
                     ::xylem::lazy_static! {
                         static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                     }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
                     ::xylem::Xylem::<::_placeholder_::_Schema_>::convert(
                         _from_placeholder_,
                         __xylem_context,
                         &*__XYLEM_ARGS,
-                    )?
+                    )
                 }},
             },
         )],
@@ -137,11 +188,23 @@ fn test_field_serde() {
         }),
         &[(
             Some(FieldFrom {
-                attrs: quote! {
+                attrs:   quote! {
                     #[serde(tagged)]
                 },
-                ident: Some(Ident::new("foo", Span::call_site())),
-                ty:    quote!(<Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::From),
+                ident:   Some(Ident::new("foo", Span::call_site())),
+                ty:      quote!(<Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::From),
+                inverse: Some(quote! {{
+                    type Args = <Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::Args;
+                    ::xylem::lazy_static! {
+                        static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
+                    }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
+                    ::xylem::XylemInverse::<::_placeholder_::_Schema_>::unconvert(
+                        _to_placeholder_,
+                        __xylem_context,
+                        &*__XYLEM_ARGS,
+                    )?
+                }}),
             }),
             FieldConv {
                 ident: Some(Ident::new("foo", Span::call_site())),
@@ -150,11 +213,12 @@ fn test_field_serde() {
                     ::xylem::lazy_static! {
                         static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
                     }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
                     ::xylem::Xylem::<::_placeholder_::_Schema_>::convert(
                         _from_placeholder_,
                         __xylem_context,
                         &*__XYLEM_ARGS,
-                    )?
+                    )
                 }},
             },
         )],
@@ -170,14 +234,20 @@ fn test_field_preserve() {
         }),
         &[(
             Some(FieldFrom {
-                attrs: quote! {},
-                ident: Some(Ident::new("foo", Span::call_site())),
-                ty:    quote!(Bar),
+                attrs:   quote! {},
+                ident:   Some(Ident::new("foo", Span::call_site())),
+                ty:      quote!(Bar),
+                inverse: Some(quote! {
+                    Ok(_to_placeholder_)?
+                }),
             }),
             FieldConv {
                 ident: Some(Ident::new("foo", Span::call_site())),
                 expr:  quote! {
-                    Ok(_from_placeholder_)?
+                    Ok(_from_placeholder_).map_err(|err| {
+                        let path = ::xylem::Context::path(&*__xylem_context).child(_segment_placeholder_);
+                        <<::_placeholder_::_Schema_ as ::xylem::Schema>::Error as ::xylem::AbstractError>::with_path(err, path)
+                    })
                 },
             },
         )],
@@ -193,14 +263,18 @@ fn test_field_transform() {
         }),
         &[(
             Some(FieldFrom {
-                attrs: quote! {},
-                ident: Some(Ident::new("foo", Span::call_site())),
-                ty:    quote!(Corge),
+                attrs:   quote! {},
+                ident:   Some(Ident::new("foo", Span::call_site())),
+                ty:      quote!(Corge),
+                inverse: None,
             }),
             FieldConv {
                 ident: Some(Ident::new("foo", Span::call_site())),
                 expr:  quote! {
-                    qux(_from_placeholder_)?
+                    qux(_from_placeholder_).map_err(|err| {
+                        let path = ::xylem::Context::path(&*__xylem_context).child(_segment_placeholder_);
+                        <<::_placeholder_::_Schema_ as ::xylem::Schema>::Error as ::xylem::AbstractError>::with_path(err, path)
+                    })
                 },
             },
         )],
@@ -216,14 +290,18 @@ fn test_field_transform_context() {
         }),
         &[(
             Some(FieldFrom {
-                attrs: quote! {},
-                ident: Some(Ident::new("foo", Span::call_site())),
-                ty:    quote!(Corge),
+                attrs:   quote! {},
+                ident:   Some(Ident::new("foo", Span::call_site())),
+                ty:      quote!(Corge),
+                inverse: None,
             }),
             FieldConv {
                 ident: Some(Ident::new("foo", Span::call_site())),
                 expr:  quote! {
-                    qux(_from_placeholder_, __xylem_context)?
+                    qux(_from_placeholder_, __xylem_context).map_err(|err| {
+                        let path = ::xylem::Context::path(&*__xylem_context).child(_segment_placeholder_);
+                        <<::_placeholder_::_Schema_ as ::xylem::Schema>::Error as ::xylem::AbstractError>::with_path(err, path)
+                    })
                 },
             },
         )],
@@ -242,7 +320,7 @@ fn test_field_default() {
             FieldConv {
                 ident: Some(Ident::new("foo", Span::call_site())),
                 expr:  quote! {
-                    qux()
+                    Ok(qux())
                 },
             },
         )],
@@ -258,9 +336,24 @@ fn test_field_args() {
         }),
         &[(
             Some(FieldFrom {
-                attrs: quote! {},
-                ident: Some(Ident::new("foo", Span::call_site())),
-                ty:    quote!(<Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::From),
+                attrs:   quote! {},
+                ident:   Some(Ident::new("foo", Span::call_site())),
+                ty:      quote!(<Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::From),
+                inverse: Some(quote! {{
+                    type Args = <Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::Args;
+                    ::xylem::lazy_static! {
+                        static ref __XYLEM_ARGS: Args = Args {
+                            foo: bar,
+                            qux: corge(1, "waldo"),
+                            ..::std::default::Default::default() };
+                    }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
+                    ::xylem::XylemInverse::<::_placeholder_::_Schema_>::unconvert(
+                        _to_placeholder_,
+                        __xylem_context,
+                        &*__XYLEM_ARGS,
+                    )?
+                }}),
             }),
             FieldConv {
                 ident: Some(Ident::new("foo", Span::call_site())),
@@ -273,11 +366,88 @@ fn test_field_args() {
                             ..::std::default::Default::default()
                         };
                     }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
                     ::xylem::Xylem::<::_placeholder_::_Schema_>::convert(
                         _from_placeholder_,
                         __xylem_context,
                         &*__XYLEM_ARGS,
+                    )
+                }},
+            },
+        )],
+    );
+}
+
+#[test]
+fn test_field_transform_inverse() {
+    test_process_field(
+        quote!({
+            #[xylem(transform = qux(Corge), inverse = grault)]
+            foo: Bar,
+        }),
+        &[(
+            Some(FieldFrom {
+                attrs:   quote! {},
+                ident:   Some(Ident::new("foo", Span::call_site())),
+                ty:      quote!(Corge),
+                inverse: Some(quote! {
+                    grault(_to_placeholder_)?
+                }),
+            }),
+            FieldConv {
+                ident: Some(Ident::new("foo", Span::call_site())),
+                expr:  quote! {
+                    qux(_from_placeholder_).map_err(|err| {
+                        let path = ::xylem::Context::path(&*__xylem_context).child(_segment_placeholder_);
+                        <<::_placeholder_::_Schema_ as ::xylem::Schema>::Error as ::xylem::AbstractError>::with_path(err, path)
+                    })
+                },
+            },
+        )],
+    );
+}
+
+#[test]
+fn test_field_spanned() {
+    test_process_field_spanned(
+        quote!({
+            foo: Bar,
+        }),
+        true,
+        &[(
+            Some(FieldFrom {
+                attrs:   quote! {},
+                ident:   Some(Ident::new("foo", Span::call_site())),
+                ty:      quote!(
+                    <::xylem::Spanned<Bar> as ::xylem::Xylem<::_placeholder_::_Schema_>>::From
+                ),
+                inverse: Some(quote! {{
+                    type Args = <Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::Args;
+                    ::xylem::lazy_static! {
+                        static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
+                    }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
+                    ::xylem::XylemInverse::<::_placeholder_::_Schema_>::unconvert(
+                        ::xylem::Spanned::new(_to_placeholder_, 0..0),
+                        __xylem_context,
+                        &*__XYLEM_ARGS,
                     )?
+                }}),
+            }),
+            FieldConv {
+                ident: Some(Ident::new("foo", Span::call_site())),
+                expr:  quote! {{
+                    type Args = <Bar as ::xylem::Xylem<::_placeholder_::_Schema_>>::Args;
+                    ::xylem::lazy_static! {
+                        static ref __XYLEM_ARGS: Args = Args { ..::std::default::Default::default() };
+                    }
+                    ::xylem::Context::set_next_segment(__xylem_context, _segment_placeholder_);
+                    <::xylem::Spanned<Bar> as ::xylem::Xylem<::_placeholder_::_Schema_>>::convert(
+                        _from_placeholder_,
+                        __xylem_context,
+                        &*__XYLEM_ARGS,
+                    )
+                    .map(::xylem::Spanned::into_value)
                 }},
             },
         )],
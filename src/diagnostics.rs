@@ -0,0 +1,64 @@
+use std::fmt;
+
+use getset::Getters;
+
+use crate::{Context, FieldPath};
+
+/// A non-fatal problem found during conversion,
+/// reported by [`Context::warn`](crate::Context::warn).
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct Warning {
+    /// The path of the value that was being converted when the warning was reported.
+    #[getset(get = "pub")]
+    path:    FieldPath,
+    /// The warning message.
+    #[getset(get = "pub")]
+    message: String,
+}
+
+impl Warning {
+    /// Creates a warning.
+    pub fn new(path: FieldPath, message: String) -> Self { Self { path, message } }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", &self.message)
+        } else {
+            write!(f, "{}: {}", &self.path, &self.message)
+        }
+    }
+}
+
+/// Stores the warnings in the root scope.
+#[derive(Default)]
+pub(crate) struct Diagnostics {
+    pub(crate) warnings: Vec<Warning>,
+}
+
+/// Appends warnings to the [`Diagnostics`] in the root scope.
+///
+/// The warnings are removed again if the current checkpoint is rolled back.
+pub(crate) fn report<C: Context>(context: &mut C, warnings: impl IntoIterator<Item = Warning>) {
+    context.journal_undo_at(0, |diagnostics: &Diagnostics| {
+        let len = diagnostics.warnings.len();
+        move |diagnostics: &mut Diagnostics| diagnostics.warnings.truncate(len)
+    });
+    root_diagnostics(context).warnings.extend(warnings);
+}
+
+/// Removes and returns the warnings in the root scope.
+///
+/// The warnings are restored if the current checkpoint is rolled back.
+pub(crate) fn take<C: Context>(context: &mut C) -> Vec<Warning> {
+    context.journal_undo_at(0, |diagnostics: &Diagnostics| {
+        let warnings = diagnostics.warnings.clone();
+        move |diagnostics: &mut Diagnostics| diagnostics.warnings = warnings
+    });
+    std::mem::take(&mut root_diagnostics(context).warnings)
+}
+
+fn root_diagnostics<C: Context>(context: &mut C) -> &mut Diagnostics {
+    context.try_get_mut_at::<Diagnostics, _>(0, Default::default).expect("root layer exists")
+}
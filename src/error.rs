@@ -0,0 +1,109 @@
+#[cfg(feature = "anyhow")]
+use std::fmt;
+
+#[cfg(feature = "anyhow")]
+use getset::Getters;
+
+use crate::{AbstractError, Context};
+
+/// Collects the errors of multiple conversions
+/// if [`Context::collect_errors`] is enabled.
+///
+/// This is used in the derived [`Xylem`](crate::Xylem) implementations
+/// and the standard collection conversions
+/// to keep converting the remaining fields after a failure.
+pub struct ErrorCollector<E> {
+    enabled: bool,
+    error:   Option<E>,
+}
+
+impl<E: AbstractError> ErrorCollector<E> {
+    /// Creates a collector using the mode of the context.
+    pub fn new(context: &impl Context) -> Self {
+        Self { enabled: context.collect_errors(), error: None }
+    }
+
+    /// Handles the result of a conversion.
+    ///
+    /// Returns `Ok(Some(value))` if the conversion succeeded.
+    /// Otherwise, if errors are collected, stores the error and returns `Ok(None)`;
+    /// if errors are not collected, returns the error immediately.
+    pub fn collect<T>(&mut self, result: Result<T, E>) -> Result<Option<T>, E> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if self.enabled => {
+                self.error = Some(match self.error.take() {
+                    Some(prev) => prev.merge(err),
+                    None => err,
+                });
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns all collected errors, if any.
+    ///
+    /// If this returns `Ok`, all previous calls to [`ErrorCollector::collect`]
+    /// have returned `Some`.
+    pub fn finish(self) -> Result<(), E> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Multiple [`anyhow::Error`]s merged by [`AbstractError::merge`].
+#[cfg(feature = "anyhow")]
+#[derive(Debug, Getters)]
+pub struct ErrorList {
+    /// The merged errors, in the order they occurred.
+    #[getset(get = "pub")]
+    errors: Vec<anyhow::Error>,
+}
+
+#[cfg(feature = "anyhow")]
+impl fmt::Display for ErrorList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, err) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", err)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl std::error::Error for ErrorList {}
+
+#[cfg(feature = "anyhow")]
+impl ErrorList {
+    pub(crate) fn merge(first: anyhow::Error, second: anyhow::Error) -> anyhow::Error {
+        let mut errors = Self::into_errors(first);
+        errors.extend(Self::into_errors(second));
+        anyhow::Error::new(ErrorList { errors })
+    }
+
+    fn into_errors(err: anyhow::Error) -> Vec<anyhow::Error> {
+        match err.downcast::<ErrorList>() {
+            Ok(list) => list.errors,
+            Err(err) => vec![err],
+        }
+    }
+
+    /// Applies `f` on each error if `err` is an `ErrorList`, or on `err` itself otherwise.
+    pub(crate) fn map(
+        err: anyhow::Error,
+        mut f: impl FnMut(anyhow::Error) -> anyhow::Error,
+    ) -> anyhow::Error {
+        match err.downcast::<ErrorList>() {
+            Ok(list) => {
+                anyhow::Error::new(ErrorList { errors: list.errors.into_iter().map(f).collect() })
+            }
+            Err(err) => f(err),
+        }
+    }
+}
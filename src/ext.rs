@@ -3,7 +3,7 @@ use std::hash::Hash;
 use std::rc::Rc;
 use std::sync::Arc;
 
-use crate::{Schema, Xylem};
+use crate::{Context, ErrorCollector, PathSegment, Schema, Xylem, XylemInverse};
 
 /// Implement this trait for a schema type to implement "standard" conversions.
 ///
@@ -32,6 +32,16 @@ impl<S: BoxSchemaExt, T: Xylem<S>> Xylem<S> for Box<T> {
     }
 }
 
+impl<S: BoxSchemaExt, T: XylemInverse<S>> XylemInverse<S> for Box<T> {
+    fn unconvert_impl(
+        self,
+        context: &mut S::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, S::Error> {
+        Ok(Box::new((*self).unconvert(context, args)?))
+    }
+}
+
 impl<T: SchemaExt> BoxSchemaExt for T {}
 
 /// Implement this trait for a schema type to support standard [`Rc`] conversion.
@@ -65,6 +75,17 @@ impl<S: RcSchemaExt, T: Xylem<S>> Xylem<S> for Rc<T> {
     }
 }
 
+/// The inner value is cloned if the [`Rc`] is shared.
+impl<S: RcSchemaExt, T: XylemInverse<S> + Clone> XylemInverse<S> for Rc<T> {
+    fn unconvert_impl(
+        self,
+        context: &mut S::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, S::Error> {
+        Ok(Box::new(Rc::unwrap_or_clone(self).unconvert(context, args)?))
+    }
+}
+
 impl<T: SchemaExt> RcSchemaExt for T {}
 
 /// Implement this trait for a schema type to support standard [`Arc`] conversion.
@@ -98,6 +119,17 @@ impl<S: ArcSchemaExt, T: Xylem<S>> Xylem<S> for Arc<T> {
     }
 }
 
+/// The inner value is cloned if the [`Arc`] is shared.
+impl<S: ArcSchemaExt, T: XylemInverse<S> + Clone> XylemInverse<S> for Arc<T> {
+    fn unconvert_impl(
+        self,
+        context: &mut S::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, S::Error> {
+        Ok(Box::new(Arc::unwrap_or_clone(self).unconvert(context, args)?))
+    }
+}
+
 impl<T: SchemaExt> ArcSchemaExt for T {}
 
 /// Implement this trait for a schema type to support standard [`Option`] conversion.
@@ -123,11 +155,26 @@ impl<S: OptionSchemaExt, T: Xylem<S>> Xylem<S> for Option<T> {
     }
 }
 
+impl<S: OptionSchemaExt, T: XylemInverse<S>> XylemInverse<S> for Option<T> {
+    fn unconvert_impl(
+        self,
+        context: &mut S::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, S::Error> {
+        Ok(match self {
+            Some(value) => Some(value.unconvert(context, args)?),
+            None => None,
+        })
+    }
+}
+
 /// Implement this trait for a schema type to support standard [`Vec`] conversion.
 ///
 /// This allows `Vec<T>` to be converted from `Vec<T::From>`,
 /// applying the conversion for `T` elementwise.
 /// The argument is passed as-is for each element.
+/// If [`Context::collect_errors`] is enabled,
+/// the errors of all elements are returned.
 pub trait VecSchemaExt: Schema {}
 
 impl<S: VecSchemaExt, T: Xylem<S>> Xylem<S> for Vec<T> {
@@ -139,7 +186,32 @@ impl<S: VecSchemaExt, T: Xylem<S>> Xylem<S> for Vec<T> {
         context: &mut S::Context,
         args: &Self::Args,
     ) -> Result<Self, S::Error> {
-        from.into_iter().map(|item| T::convert(item, context, args)).collect()
+        let mut errors = ErrorCollector::new(context);
+        let mut items = Vec::with_capacity(from.len());
+        for (index, item) in from.into_iter().enumerate() {
+            context.set_next_segment(PathSegment::Index(index));
+            if let Some(item) = errors.collect(T::convert(item, context, args))? {
+                items.push(item);
+            }
+        }
+        errors.finish()?;
+        Ok(items)
+    }
+}
+
+impl<S: VecSchemaExt, T: XylemInverse<S>> XylemInverse<S> for Vec<T> {
+    fn unconvert_impl(
+        self,
+        context: &mut S::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, S::Error> {
+        self.into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                context.set_next_segment(PathSegment::Index(index));
+                item.unconvert(context, args)
+            })
+            .collect()
     }
 }
 
@@ -151,6 +223,12 @@ impl<T: SchemaExt> VecSchemaExt for T {}
 /// applying the conversion for `K` for k.
 /// The value argument is passed as-is for each value.
 /// No conversion arguments can be passed to the key type (the default value is always used).
+/// The position of the entry in iteration order is used in the [`PathSegment`],
+/// which is unspecified for [`HashMap`]s.
+/// For the same reason, the inverse conversion may visit the entries
+/// in a different order than the conversion (see [`XylemInverse`]).
+/// If [`Context::collect_errors`] is enabled,
+/// the errors of all keys and values are returned.
 pub trait HashMapSchemaExt: Schema {}
 
 impl<S: HashMapSchemaExt, K: Xylem<S>, V: Xylem<S>> Xylem<S> for HashMap<K, V>
@@ -167,12 +245,39 @@ where
         context: &mut S::Context,
         args: &Self::Args,
     ) -> Result<Self, S::Error> {
-        from.into_iter()
-            .map(|(key, value)| {
-                Ok((
-                    K::convert(key, context, &Default::default())?,
-                    V::convert(value, context, args)?,
-                ))
+        let mut errors = ErrorCollector::new(context);
+        let mut map = Self::new();
+        for (index, (key, value)) in from.into_iter().enumerate() {
+            context.set_next_segment(PathSegment::MapKey(index));
+            let key = errors.collect(K::convert(key, context, &Default::default()))?;
+            context.set_next_segment(PathSegment::Index(index));
+            let value = errors.collect(V::convert(value, context, args))?;
+            if let (Some(key), Some(value)) = (key, value) {
+                map.insert(key, value);
+            }
+        }
+        errors.finish()?;
+        Ok(map)
+    }
+}
+
+impl<S: HashMapSchemaExt, K: XylemInverse<S>, V: XylemInverse<S>> XylemInverse<S> for HashMap<K, V>
+where
+    K: Eq + Hash,
+    K::From: Eq + Hash,
+{
+    fn unconvert_impl(
+        self,
+        context: &mut S::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, S::Error> {
+        self.into_iter()
+            .enumerate()
+            .map(|(index, (key, value))| {
+                context.set_next_segment(PathSegment::MapKey(index));
+                let key = key.unconvert(context, &Default::default())?;
+                context.set_next_segment(PathSegment::Index(index));
+                Ok((key, value.unconvert(context, args)?))
             })
             .collect()
     }
@@ -186,6 +291,9 @@ impl<T: SchemaExt> HashMapSchemaExt for T {}
 /// applying the conversion for `K` for k.
 /// The value argument is passed as-is for each value.
 /// No conversion arguments can be passed to the key type (the default value is always used).
+/// The position of the entry in key order is used in the [`PathSegment`].
+/// If [`Context::collect_errors`] is enabled,
+/// the errors of all keys and values are returned.
 pub trait BTreeMapSchemaExt: Schema {}
 
 impl<S: BTreeMapSchemaExt, K: Xylem<S>, V: Xylem<S>> Xylem<S> for BTreeMap<K, V>
@@ -202,12 +310,40 @@ where
         context: &mut S::Context,
         args: &Self::Args,
     ) -> Result<Self, S::Error> {
-        from.into_iter()
-            .map(|(key, value)| {
-                Ok((
-                    K::convert(key, context, &Default::default())?,
-                    V::convert(value, context, args)?,
-                ))
+        let mut errors = ErrorCollector::new(context);
+        let mut map = Self::new();
+        for (index, (key, value)) in from.into_iter().enumerate() {
+            context.set_next_segment(PathSegment::MapKey(index));
+            let key = errors.collect(K::convert(key, context, &Default::default()))?;
+            context.set_next_segment(PathSegment::Index(index));
+            let value = errors.collect(V::convert(value, context, args))?;
+            if let (Some(key), Some(value)) = (key, value) {
+                map.insert(key, value);
+            }
+        }
+        errors.finish()?;
+        Ok(map)
+    }
+}
+
+impl<S: BTreeMapSchemaExt, K: XylemInverse<S>, V: XylemInverse<S>> XylemInverse<S>
+    for BTreeMap<K, V>
+where
+    K: Eq + Ord,
+    K::From: Eq + Ord,
+{
+    fn unconvert_impl(
+        self,
+        context: &mut S::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, S::Error> {
+        self.into_iter()
+            .enumerate()
+            .map(|(index, (key, value))| {
+                context.set_next_segment(PathSegment::MapKey(index));
+                let key = key.unconvert(context, &Default::default())?;
+                context.set_next_segment(PathSegment::Index(index));
+                Ok((key, value.unconvert(context, args)?))
             })
             .collect()
     }
@@ -0,0 +1,202 @@
+use std::any::TypeId;
+use std::mem;
+use std::rc::Rc;
+
+use crate::{AbstractError, Context, LayerSelector, Schema};
+
+/// A function called when a scope ends.
+pub type Finalizer<S> = Rc<dyn Fn(&mut <S as Schema>::Context) -> Result<(), <S as Schema>::Error>>;
+
+/// The finalizers registered in a scope.
+///
+/// This is keyed by the context and error types instead of the schema,
+/// so that schemas inheriting each other with [`InheritSchema`](crate::InheritSchema)
+/// share their finalizers.
+struct Finalizers<C, E> {
+    list: Vec<Deferred<C, E>>,
+}
+
+/// A [`Finalizer`] identified by the context and error types instead of the schema.
+type ErasedFinalizer<C, E> = Rc<dyn Fn(&mut C) -> Result<(), E>>;
+
+/// A registered finalizer.
+struct Deferred<C, E> {
+    finalizer: ErasedFinalizer<C, E>,
+    /// Whether the finalizer is kept in the root scope
+    /// if the top-level conversion that registered it fails.
+    retained:  bool,
+}
+
+impl<C, E> Clone for Deferred<C, E> {
+    fn clone(&self) -> Self {
+        Self { finalizer: Rc::clone(&self.finalizer), retained: self.retained }
+    }
+}
+
+/// The finalizers storage type for the schema `S`.
+type FinalizersOf<S> = Finalizers<<S as Schema>::Context, <S as Schema>::Error>;
+
+impl<C, E> Default for Finalizers<C, E> {
+    fn default() -> Self { Self { list: Vec::new() } }
+}
+
+/// Journals the removal of the finalizers pushed to the layer at the index after this call.
+fn journal_push<S: Schema + ?Sized>(context: &mut <S as Schema>::Context, layer: usize) {
+    context.journal_undo_at(layer, |finalizers: &FinalizersOf<S>| {
+        let len = finalizers.list.len();
+        move |finalizers: &mut FinalizersOf<S>| finalizers.list.truncate(len)
+    });
+}
+
+/// Journals the restoration of the finalizers in the layer at the index
+/// before they are taken out of the list.
+fn journal_take<S: Schema + ?Sized>(context: &mut <S as Schema>::Context, layer: usize) {
+    context.journal_undo_at(layer, |finalizers: &FinalizersOf<S>| {
+        let list = finalizers.list.clone();
+        move |finalizers: &mut FinalizersOf<S>| finalizers.list = list
+    });
+}
+
+/// Registers a finalizer to be called when the newest layer of `scope` ends.
+///
+/// Finalizers are called in registration order
+/// after the conversion of the scope type has succeeded,
+/// while the scope is still in the stack.
+/// If the conversion fails, the finalizers are discarded.
+///
+/// Finalizers of the root scope `()` are only called by [`finalize`].
+/// Root finalizers registered during a top-level conversion
+/// are discarded if that conversion fails.
+///
+/// # Panics
+/// Panics if `scope` is not in the stack.
+pub fn defer<S: Schema + ?Sized>(
+    context: &mut <S as Schema>::Context,
+    scope: TypeId,
+    finalizer: Finalizer<S>,
+) {
+    let layer =
+        context.find_layer(scope, LayerSelector::Nearest).expect("Scope is not in the stack");
+    defer_at::<S>(context, layer, finalizer);
+}
+
+/// Registers a finalizer to be called when the layer at the index ends.
+///
+/// This is equivalent to [`defer`],
+/// but the layer is selected with [`Context::find_layer`].
+///
+/// # Panics
+/// Panics if there is no layer at the index.
+pub fn defer_at<S: Schema + ?Sized>(
+    context: &mut <S as Schema>::Context,
+    layer: usize,
+    finalizer: Finalizer<S>,
+) {
+    push_finalizer::<S>(context, layer, Deferred { finalizer, retained: false });
+}
+
+/// Registers a root finalizer that is kept
+/// even if the current top-level conversion fails,
+/// for state that outlives the conversion.
+#[cfg(feature = "id")]
+pub(crate) fn defer_retained<S: Schema + ?Sized>(
+    context: &mut <S as Schema>::Context,
+    finalizer: Finalizer<S>,
+) {
+    push_finalizer::<S>(context, 0, Deferred { finalizer, retained: true });
+}
+
+fn push_finalizer<S: Schema + ?Sized>(
+    context: &mut <S as Schema>::Context,
+    layer: usize,
+    deferred: Deferred<S::Context, S::Error>,
+) {
+    journal_push::<S>(context, layer);
+    context
+        .try_get_mut_at::<FinalizersOf<S>, _>(layer, Default::default)
+        .expect("No layer at the index")
+        .list
+        .push(deferred);
+}
+
+/// Returns the number of root finalizers if the context is at the root scope,
+/// i.e. before a top-level conversion starts.
+///
+/// The result is passed to [`discard_root_finalizers`] if the conversion fails.
+pub(crate) fn root_finalizer_mark<S: Schema + ?Sized>(
+    context: &<S as Schema>::Context,
+) -> Option<usize> {
+    if context.nth_last_scope(1).is_some() {
+        return None;
+    }
+    Some(context.get_at::<FinalizersOf<S>>(0).map_or(0, |finalizers| finalizers.list.len()))
+}
+
+/// Discards the root finalizers registered after the mark,
+/// except those registered with [`defer_retained`].
+pub(crate) fn discard_root_finalizers<S: Schema + ?Sized>(
+    context: &mut <S as Schema>::Context,
+    mark: usize,
+) {
+    let count = context.get_at::<FinalizersOf<S>>(0).map_or(0, |finalizers| finalizers.list.len());
+    if count <= mark {
+        return;
+    }
+
+    journal_take::<S>(context, 0);
+    let finalizers = context
+        .try_get_mut_at::<FinalizersOf<S>, _>(0, Default::default)
+        .expect("root layer exists");
+    let discarded = finalizers.list.split_off(mark);
+    finalizers.list.extend(discarded.into_iter().filter(|deferred| deferred.retained));
+}
+
+/// Calls the finalizers registered in the root scope `()`,
+/// e.g. to resolve global [`LazyId`](crate::LazyId)s.
+///
+/// This should be called after converting all values that share the context.
+/// Finalizers are only called once,
+/// so this can be called again after converting more values.
+pub fn finalize<S: Schema + ?Sized>(
+    context: &mut <S as Schema>::Context,
+) -> Result<(), <S as Schema>::Error> {
+    run_finalizers::<S>(context, TypeId::of::<()>())
+}
+
+/// Calls the finalizers registered in the newest layer of `scope`.
+///
+/// If finalizers fail, their errors are merged with [`AbstractError::merge`].
+pub(crate) fn run_finalizers<S: Schema + ?Sized>(
+    context: &mut <S as Schema>::Context,
+    scope: TypeId,
+) -> Result<(), <S as Schema>::Error> {
+    let mut error: Option<S::Error> = None;
+
+    // finalizers may register more finalizers
+    while context
+        .get::<FinalizersOf<S>>(scope)
+        .is_some_and(|finalizers| !finalizers.list.is_empty())
+    {
+        let layer = context.find_layer(scope, LayerSelector::Nearest).expect("storage was found");
+        journal_take::<S>(context, layer);
+        let list = mem::take(
+            &mut context
+                .try_get_mut_at::<FinalizersOf<S>, _>(layer, Default::default)
+                .expect("layer was found above")
+                .list,
+        );
+        for Deferred { finalizer, .. } in list {
+            if let Err(err) = finalizer(context) {
+                error = Some(match error {
+                    Some(prev) => prev.merge(err),
+                    None => err,
+                });
+            }
+        }
+    }
+
+    match error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
@@ -5,15 +5,31 @@
 
 use core::fmt;
 use std::any::{type_name, TypeId};
-use std::collections::BTreeMap;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
 use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Deref, Range};
+use std::rc::Rc;
+use std::sync::{Arc, OnceLock};
 
 use getset::{CopyGetters, Getters, MutGetters};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{AbstractError, Context, NoArgs, Schema, Xylem};
+use crate::finalize::defer_retained;
+use crate::{
+    defer_at, diagnostics, AbstractError, Context, FieldPath, LayerSelector, NoArgs, Schema,
+    Warning, Xylem, XylemInverse,
+};
+
+mod diff;
+pub use diff::{IdDiff, IdTypes, ScopeDiff};
+mod collections;
+pub use collections::{IdMap, IdVec};
+mod registry;
+pub use registry::IdRegistry;
 
 /// An identifier for type `X`.
 ///
@@ -26,12 +42,26 @@ pub struct Id<S, X> {
 
 impl<S, X> Id<S, X> {
     /// Creates a new identifier.
-    pub fn new(index: usize) -> Self {
-        Self { index: index.try_into().expect("Too many identifiers"), _ph: PhantomData }
+    ///
+    /// # Panics
+    /// Panics if `index` does not fit in a `u32`.
+    /// Use [`Id::try_new`] if the index is not trusted.
+    pub fn new(index: usize) -> Self { Self::try_new(index).expect("Too many identifiers") }
+
+    /// Creates a new identifier,
+    /// or returns `None` if `index` does not fit in a `u32`.
+    pub fn try_new(index: usize) -> Option<Self> {
+        Some(Self { index: index.try_into().ok()?, _ph: PhantomData })
     }
 
     /// Returns the index of the identifier.
     pub fn index(&self) -> usize { self.index.try_into().expect("Too many identifiers") }
+
+    /// Creates the identifier of a key for types with [`IdIndexing::Hashed`],
+    /// e.g. to refer to an ID without converting the config.
+    pub fn hashed<K: fmt::Display + ?Sized>(key: &K) -> Self {
+        Self { index: hash_key(key), _ph: PhantomData }
+    }
 }
 
 // We need to manually implement these traits because
@@ -44,7 +74,7 @@ impl<S, X> fmt::Debug for Id<S, X> {
 }
 
 impl<S, X> Clone for Id<S, X> {
-    fn clone(&self) -> Self { Self { index: self.index, _ph: PhantomData } }
+    fn clone(&self) -> Self { *self }
 }
 
 impl<S, X> Copy for Id<S, X> {}
@@ -71,8 +101,8 @@ impl<S, X> Hash for Id<S, X> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.index.hash(state); }
 }
 
-impl<S: Schema, X: Identifiable<S>> Xylem<S> for Id<S, X> {
-    type From = String;
+impl<S: Schema, X: KeyedIdentifiable<S>> Xylem<S> for Id<S, X> {
+    type From = X::Key;
     type Args = IdArgs;
 
     #[inline]
@@ -81,134 +111,587 @@ impl<S: Schema, X: Identifiable<S>> Xylem<S> for Id<S, X> {
         context: &mut <S as Schema>::Context,
         args: &Self::Args,
     ) -> Result<Self, <S as Schema>::Error> {
-        let index = {
-            if args.new {
-                let counter =
-                    context.get_mut::<IdCounter<X>, _>(TypeId::of::<X::Scope>(), Default::default);
+        let index = if args.new {
+            let (layer, scope) = declaring_layers::<S, X>(context)?;
+            let span = context.span();
+            let counter = counter_at::<S, X>(context, scope);
+
+            if let Some((other, _)) = counter.resolve(&from) {
+                let first = counter.span(other);
+                return Err(S::Error::new(format_args!(
+                    "Duplicate ID {}{}",
+                    &from,
+                    duplicate_spans(&first, &span),
+                ))
+                .with_spans(known_spans([first, span])));
+            }
+            if let Some(other) = counter.collision(&from) {
+                return Err(S::Error::new(format_args!(
+                    "Hash collision between IDs {} and {}{}",
+                    other,
+                    &from,
+                    reference_span(&span),
+                ))
+                .with_spans(known_spans([span])));
+            }
+            let index = counter.next_index(&from);
+            if Id::<S, X>::try_new(index).is_none() {
+                return Err(too_many_error::<S, X>());
+            }
+            context.journal_undo_at(scope, |counter: &IdCounter<S, X>| counter.undo_declare(&from));
+            let position = counter_at::<S, X>(context, scope).declare(from.clone(), span);
+
+            declare_current::<S, X>(context, layer, index, &from)?;
+            register_resolver::<S, X>(context);
 
-                if counter.names.iter().any(|other| other == &from) {
-                    return Err(S::Error::new(format_args!("Duplicate ID {}", &from)));
+            if args.track || X::report_unused() {
+                let parent_ids = parent_path(context, scope);
+                record_declaration::<S, X>(context, &parent_ids, index, &from);
+
+                if args.track {
+                    context.journal_undo_at(0, |store: &GlobalIdStore<S, X>| {
+                        store.undo_set(&parent_ids, position)
+                    });
+                    context
+                        .try_get_mut_at::<GlobalIdStore<S, X>, _>(0, Default::default)
+                        .expect("root layer exists")
+                        .ids
+                        .entry(parent_ids)
+                        .or_default()
+                        .set(position, from);
                 }
-                let index = counter
-                    .names
-                    .len()
-                    .try_into()
-                    .expect("More than u32::MAX_VALUE IDs registered");
-                counter.names.push(from.clone());
-                index
-            } else {
-                let index = match context.get::<IdCounter<X>>(TypeId::of::<X::Scope>()) {
-                    Some(counter) => {
-                        let index = counter.names.iter().position(|other| other == &from);
-                        match index {
-                            Some(index) => index,
-                            None => {
-                                return Err(S::Error::new(format_args!("Unknown ID {}", &from)))
-                            }
-                        }
-                    }
-                    None => {
-                        let mut index = None;
-                        for import in context.get_each::<ImportScope>() {
-                            if let Some(id) = import.map.get(&TypeId::of::<X>()) {
-                                let store =
-                                    match context.get::<GlobalIdStore<S, X>>(TypeId::of::<()>()) {
-                                        Some(store) => store,
-                                        None => {
-                                            return Err(S::Error::new(format_args!(
-                                                "Attempted to import scope for {}, but it was not \
-                                                 tracked before. Did you forget to \
-                                                 #[xylem(args(targs = true, track = true))]?",
-                                                type_name::<X>()
-                                            )));
-                                        }
-                                    };
-                                let ids = match store.ids.get(id) {
-                                    Some(ids) => ids,
-                                    None => {
-                                        return Err(S::Error::new(
-                                            "Scope was successfully imported but the ID is not \
-                                             tracked",
-                                        ));
-                                    }
-                                };
-                                index = match ids.iter().position(|id| id == &from) {
-                                    Some(index) => Some(index),
-                                    None => {
-                                        return Err(S::Error::new(format_args!(
-                                            "Unknown ID {}",
-                                            &from
-                                        )))
-                                    }
-                                };
-                                break;
-                            }
-                        }
+            }
+
+            index
+        } else if let Some((separator, qualified)) = args
+            .separator
+            .map(|separator| (separator, from.to_string()))
+            .filter(|(separator, qualified)| qualified.contains(separator))
+        {
+            let mut path = resolve_qualified::<S, X>(context, &qualified, separator)?;
+            let index = path.pop().expect("segments is non-empty");
+            record_reference::<S, X>(context, &path, index);
+
+            register_imports::<S>(context, args, |_| path, index)?;
 
-                        match index {
-                            Some(index) => index,
-                            None => {
-                                return Err(S::Error::new(
-                                    "Use of ID before registering the first one. Did you forget \
-                                     to #[xylem(args(new = true))] and put it as the first field?",
-                                ))
-                            }
+            index
+        } else {
+            let scope = reference_layer::<S, X>(context);
+            let (index, imported_path) =
+                match scope.and_then(|scope| context.get_at::<IdCounter<S, X>>(scope)) {
+                    Some(counter) => (counter.resolve(&from), None),
+                    None => match imported_names::<S, X>(context)? {
+                        Some((path, names)) => {
+                            let index = names
+                                .position(&from)
+                                .map(|position| (X::indexing().index(&from, position), None));
+                            (index, Some(path.to_vec()))
                         }
-                    }
+                        None if scope.is_none() => return Err(not_ancestor_error::<S, X>()),
+                        None => return Err(no_declaration_error::<S>()),
+                    },
                 };
-
-                let import = context.get_mut::<ImportScope, _>(
-                    context.nth_last_scope(1).expect("Stack too shallow"),
-                    Default::default,
-                );
-                for &imported in &args.import {
-                    import.map.insert(imported, vec![index]); // TODO support imports with more than 2 levels of scopes
+            let index = match index {
+                Some((index, alias_of)) => {
+                    if let Some(name) = alias_of {
+                        let path = context.path();
+                        warn_alias::<S, _>(context, path, &from, &name);
+                    }
+                    index
+                }
+                None => {
+                    let span = context.span();
+                    return Err(S::Error::new(format_args!(
+                        "Unknown ID {}{}",
+                        &from,
+                        reference_span(&span),
+                    ))
+                    .with_spans(known_spans([span])));
                 }
+            };
 
-                index.try_into().expect("More than u32::MAX_VALUE IDs registered")
+            let scope_path = |context: &_| match imported_path {
+                Some(path) => path,
+                None => parent_path(context, scope.expect("counter was found in the scope")),
+            };
+            if X::report_unused() || !args.import.is_empty() {
+                let scope_path = scope_path(context);
+                record_reference::<S, X>(context, &scope_path, index);
+                register_imports::<S>(context, args, |_| scope_path, index)?;
             }
+
+            index
+        };
+
+        let id = match Id::try_new(index) {
+            Some(id) => id,
+            None => return Err(too_many_error::<S, X>()),
         };
 
-        let id = Id { index, _ph: PhantomData };
+        Ok(id)
+    }
+}
+
+/// Maps the identifier back to the original string.
+///
+/// Declaring identifiers are resolved from the names recorded in the context,
+/// so the inverse conversion should use the same context as the conversion.
+/// Since scoped namespaces are dropped with their scope,
+/// scoped declarations can only be resolved if they were declared with `track = true`.
+impl<S: Schema, X: KeyedIdentifiable<S>> XylemInverse<S> for Id<S, X> {
+    #[inline]
+    fn unconvert_impl(
+        self,
+        context: &mut <S as Schema>::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, <S as Schema>::Error> {
+        let index = self.index();
 
-        if args.new {
-            let mut new = false;
-            let current_id = context.get_mut::<CurrentId, _>(TypeId::of::<X>(), || {
-                new = true;
-                CurrentId {
-                    id:     id.index(),
-                    parent: TypeId::of::<X::Scope>(),
-                    string: from.clone(),
+        let name = if args.new {
+            let (layer, scope) = declaring_layers::<S, X>(context)?;
+            let recorded = context
+                .get_at::<IdCounter<S, X>>(scope)
+                .and_then(|counter| counter.name(index))
+                .cloned();
+            let name = match recorded {
+                Some(name) => name,
+                None => {
+                    let parent_ids = parent_path(context, scope);
+                    let tracked = context
+                        .get::<GlobalIdStore<S, X>>(TypeId::of::<()>())
+                        .and_then(|store| store.ids.get(&parent_ids))
+                        .filter(|names| X::indexing().position(names, index).is_some())
+                        .cloned();
+                    let not_recorded = || {
+                        S::Error::new(format_args!(
+                            "The name of {} ID {} was not recorded. Did you forget to \
+                             #[xylem(args(new = true, track = true))]?",
+                            type_name::<X>(),
+                            index,
+                        ))
+                    };
+                    let tracked = match tracked {
+                        Some(tracked) => tracked,
+                        None => return Err(not_recorded()),
+                    };
+
+                    // restore the namespace so that subsequent references can be resolved
+                    let known = counter_at::<S, X>(context, scope).names.len();
+                    context.journal_undo_at(scope, |_: &IdCounter<S, X>| {
+                        move |counter: &mut IdCounter<S, X>| counter.truncate(known)
+                    });
+                    let counter = counter_at::<S, X>(context, scope);
+                    for name in tracked.iter().skip(known) {
+                        counter.restore(name.clone());
+                    }
+
+                    match counter.name(index) {
+                        Some(name) => name.clone(),
+                        None => return Err(not_recorded()),
+                    }
                 }
-            });
-            if !new {
-                return Err(S::Error::new(format_args!(
-                    "Multiple new IDs defined for {} ({}, {})",
-                    type_name::<X>(),
-                    id.index(),
-                    current_id.id,
-                )));
-            }
+            };
 
-            if args.track {
-                let mut parent_ids = Vec::new();
+            declare_current::<S, X>(context, layer, index, &name)?;
 
-                let mut next_parent = TypeId::of::<X::Scope>();
-                while let Some(parent_id) = context.get::<CurrentId>(next_parent) {
-                    parent_ids.push(parent_id.id);
-                    next_parent = parent_id.parent;
+            name
+        } else if args.separator.is_some() {
+            return Err(S::Error::new(format_args!(
+                "{} IDs with a separator cannot be unconverted, because the scopes of qualified \
+                 IDs are not recorded",
+                type_name::<X>(),
+            )));
+        } else {
+            let scope = reference_layer::<S, X>(context);
+            let (name, imported_path) =
+                match scope.and_then(|scope| context.get_at::<IdCounter<S, X>>(scope)) {
+                    Some(counter) => (counter.name(index).cloned(), None),
+                    None => match imported_names::<S, X>(context)? {
+                        Some((path, names)) => {
+                            let name = X::indexing()
+                                .position(names, index)
+                                .and_then(|position| names.name(position))
+                                .cloned();
+                            (name, Some(path.to_vec()))
+                        }
+                        None => return Err(no_declaration_error::<S>()),
+                    },
+                };
+            let name = match name {
+                Some(name) => name,
+                None => {
+                    return Err(S::Error::new(format_args!(
+                        "Unknown {} ID {}",
+                        type_name::<X>(),
+                        index
+                    )))
                 }
+            };
 
-                parent_ids.reverse();
+            let scope_path = |context: &_| match imported_path {
+                Some(path) => path,
+                None => parent_path(context, scope.expect("counter was found in the scope")),
+            };
+            register_imports::<S>(context, args, scope_path, index)?;
 
-                let store =
-                    context.get_mut::<GlobalIdStore<S, X>, _>(TypeId::of::<()>(), Default::default);
-                store.ids.entry(parent_ids).or_default().push(from);
-            }
+            name
+        };
+
+        Ok(name)
+    }
+}
+
+/// Resolves a qualified reference to an `X` ID,
+/// i.e. the names of `X` and its scope objects separated by `separator`,
+/// starting from a global ID.
+///
+/// Returns the ID path of the referenced object,
+/// i.e. the indices of its scope objects followed by its own index.
+fn resolve_qualified<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &<S as Schema>::Context,
+    from: &str,
+    separator: &str,
+) -> Result<Vec<usize>, <S as Schema>::Error> {
+    let segments: Vec<&str> = from.split(separator).collect();
+    let unknown = |segment: &str| {
+        let span = context.span();
+        S::Error::new(format_args!("Unknown ID {} in {}{}", segment, from, reference_span(&span)))
+            .with_spans(known_spans([span]))
+    };
+
+    // the lookups of each segment, starting from the last segment
+    let mut lookups: Vec<IdLookup<S>> = vec![lookup_tracked::<S, X>];
+    let mut scope = TypeId::of::<X::Scope>();
+    while lookups.len() < segments.len() {
+        if scope == TypeId::of::<()>() {
+            return Err(S::Error::new(format_args!("Too many scopes in qualified ID {}", from)));
         }
+        let resolver = context
+            .get::<IdResolvers<S>>(TypeId::of::<()>())
+            .and_then(|resolvers| resolvers.types.get(&scope));
+        let resolver = match resolver {
+            Some(resolver) => resolver,
+            // no IDs of the scope type were declared
+            None => return Err(unknown(segments[segments.len() - 1 - lookups.len()])),
+        };
+        lookups.push(resolver.lookup);
+        scope = resolver.scope;
+    }
+    if scope != TypeId::of::<()>() {
+        return Err(S::Error::new(format_args!(
+            "Qualified ID {} must start from a global ID",
+            from
+        )));
+    }
 
-        Ok(id)
+    let mut path = Vec::new();
+    for (segment, lookup) in segments.iter().zip(lookups.iter().rev()) {
+        match lookup(context, &path, segment)? {
+            Some(index) => path.push(index),
+            None => return Err(unknown(segment)),
+        }
+    }
+    Ok(path)
+}
+
+/// Looks up the index of a name in the `X::Scope` object with the ID path `parent`,
+/// where the name is the display form of the key.
+type IdLookup<S> =
+    fn(&<S as Schema>::Context, &[usize], &str) -> Result<Option<usize>, <S as Schema>::Error>;
+
+/// Looks up an `X` ID in the root scope if `X` is global,
+/// or in the IDs tracked in the [`GlobalIdStore`] otherwise.
+///
+/// Names that cannot be parsed with [`KeyedIdentifiable::parse_key`] are unknown.
+fn lookup_tracked<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &<S as Schema>::Context,
+    parent: &[usize],
+    name: &str,
+) -> Result<Option<usize>, <S as Schema>::Error> {
+    let key = X::parse_key(name);
+    if parent.is_empty() {
+        let counter = context.get::<IdCounter<S, X>>(TypeId::of::<()>());
+        return Ok(counter.zip(key).and_then(|(counter, key)| counter.lookup(&key)));
+    }
+
+    match context.get::<GlobalIdStore<S, X>>(TypeId::of::<()>()) {
+        Some(store) => Ok(key.and_then(|key| {
+            let position = store.ids.get(parent)?.position(&key)?;
+            Some(X::indexing().index(&key, position))
+        })),
+        None => Err(S::Error::new(format_args!(
+            "Attempted to resolve a qualified {} ID, but it was not tracked before. Did you \
+             forget to #[xylem(args(new = true, track = true))]?",
+            type_name::<X>()
+        ))),
+    }
+}
+
+/// Registers the lookup of `X` IDs for qualified references,
+/// which cannot name the scope types statically.
+fn register_resolver<S: Schema, X: KeyedIdentifiable<S>>(context: &mut <S as Schema>::Context) {
+    let registered = context
+        .get::<IdResolvers<S>>(TypeId::of::<()>())
+        .is_some_and(|resolvers| resolvers.types.contains_key(&TypeId::of::<X>()));
+    if !registered {
+        context
+            .get_mut_journaled::<IdResolvers<S>, _>(TypeId::of::<()>(), Default::default)
+            .types
+            .insert(
+                TypeId::of::<X>(),
+                IdResolver { scope: TypeId::of::<X::Scope>(), lookup: lookup_tracked::<S, X> },
+            );
+    }
+}
+
+/// The type-erased lookups of the declared ID types, keyed by the type ID of `X`.
+struct IdResolvers<S: Schema> {
+    types: HashMap<TypeId, IdResolver<S>>,
+}
+
+struct IdResolver<S: Schema> {
+    /// The type ID of `X::Scope`.
+    scope:  TypeId,
+    lookup: IdLookup<S>,
+}
+
+impl<S: Schema> Default for IdResolvers<S> {
+    fn default() -> Self { Self { types: HashMap::new() } }
+}
+
+impl<S: Schema> Clone for IdResolvers<S> {
+    fn clone(&self) -> Self { Self { types: self.types.clone() } }
+}
+
+impl<S: Schema> Clone for IdResolver<S> {
+    fn clone(&self) -> Self { Self { scope: self.scope, lookup: self.lookup } }
+}
+
+/// The ID path of an imported scope object and the keys in it.
+type ImportedNames<'a, K> = (&'a [usize], &'a IdNames<K>);
+
+/// Looks up the keys of the imported scope for `X`, if any.
+fn imported_names<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &<S as Schema>::Context,
+) -> Result<Option<ImportedNames<'_, X::Key>>, <S as Schema>::Error> {
+    for import in context.get_each::<ImportScope>() {
+        if let Some(id) = import.map.get(&TypeId::of::<X>()) {
+            let store = match context.get::<GlobalIdStore<S, X>>(TypeId::of::<()>()) {
+                Some(store) => store,
+                None => {
+                    return Err(S::Error::new(format_args!(
+                        "Attempted to import scope for {}, but it was not tracked before. Did you \
+                         forget to #[xylem(args(targs = true, track = true))]?",
+                        type_name::<X>()
+                    )));
+                }
+            };
+            return match store.ids.get(id) {
+                Some(ids) => Ok(Some((id, ids))),
+                None => {
+                    Err(S::Error::new("Scope was successfully imported but the ID is not tracked"))
+                }
+            };
+        }
+    }
+
+    Ok(None)
+}
+
+fn no_declaration_error<S: Schema>() -> <S as Schema>::Error {
+    S::Error::new(
+        "Use of ID before registering the first one. Did you forget to #[xylem(args(new = true))] \
+         and put it as the first field?",
+    )
+}
+
+/// Describes the source spans of a duplicate declaration for error messages.
+fn duplicate_spans(first: &Option<Range<usize>>, second: &Option<Range<usize>>) -> String {
+    if first.is_none() && second.is_none() {
+        return String::new();
+    }
+
+    let describe = |span: &Option<Range<usize>>| match span {
+        Some(span) => format!("{:?}", span),
+        None => String::from("an unknown location"),
+    };
+    format!(" (declared at {} and {})", describe(first), describe(second))
+}
+
+/// Collects the known spans for [`AbstractError::with_spans`].
+fn known_spans<const N: usize>(spans: [Option<Range<usize>>; N]) -> Vec<Range<usize>> {
+    spans.into_iter().flatten().collect()
+}
+
+/// Describes the source span of a reference for error messages.
+fn reference_span(span: &Option<Range<usize>>) -> String {
+    match span {
+        Some(span) => format!(" at {:?}", span),
+        None => String::new(),
+    }
+}
+
+/// Reports a reference that was resolved through an [`IdAliases`] alias.
+fn warn_alias<S: Schema, K: IdKey>(
+    context: &mut <S as Schema>::Context,
+    path: FieldPath,
+    alias: &K,
+    name: &K,
+) {
+    let message = format!("ID {} is a deprecated alias of {}", alias, name);
+    diagnostics::report(context, [Warning::new(path, message)]);
+}
+
+/// Imports the scopes requested in `args` into the parent scope.
+///
+/// The imported scope is the referenced object at `index`
+/// in the scope object with the ID path returned by `scope_path`,
+/// which is only computed if there are scopes to import.
+fn register_imports<S: Schema>(
+    context: &mut <S as Schema>::Context,
+    args: &IdArgs,
+    scope_path: impl FnOnce(&<S as Schema>::Context) -> Vec<usize>,
+    index: usize,
+) -> Result<(), <S as Schema>::Error> {
+    if args.import.is_empty() {
+        return Ok(());
+    }
+
+    let parent = match context.nth_last_scope(1) {
+        Some(parent) => parent,
+        None => return Err(S::Error::new("Cannot import scopes without a parent scope")),
+    };
+    let mut path = scope_path(context);
+    path.push(index);
+    let import = context.get_mut_journaled::<ImportScope, _>(parent, Default::default);
+    for &imported in &args.import {
+        import.map.insert(imported, path.clone());
+    }
+
+    Ok(())
+}
+
+/// Returns the keys of the `X` IDs declared in the root scope.
+pub(crate) fn root_names<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &<S as Schema>::Context,
+) -> &[Option<X::Key>] {
+    match context.get::<IdCounter<S, X>>(TypeId::of::<()>()) {
+        Some(counter) => counter.names.as_slice(),
+        None => &[],
+    }
+}
+
+/// Returns the key of a global `X` ID declared in the root scope.
+pub(crate) fn root_name<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &<S as Schema>::Context,
+    id: Id<S, X>,
+) -> Option<&X::Key> {
+    context.get::<IdCounter<S, X>>(TypeId::of::<()>())?.name(id.index())
+}
+
+/// Finds the layers that a new `X` ID is declared in,
+/// i.e. the nearest `X` layer and the nearest `X::Scope` layer below it.
+///
+/// Returns the indices of the `X` layer and the `X::Scope` layer.
+fn declaring_layers<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &<S as Schema>::Context,
+) -> Result<(usize, usize), <S as Schema>::Error> {
+    let layer = match context.find_layer(TypeId::of::<X>(), LayerSelector::Nearest) {
+        Some(layer) => layer,
+        None => {
+            return Err(S::Error::new(format_args!(
+                "New {} IDs can only be declared in the conversion of {}",
+                type_name::<X>(),
+                type_name::<X>()
+            )))
+        }
+    };
+    match context.find_layer(TypeId::of::<X::Scope>(), LayerSelector::Below(layer)) {
+        Some(scope) => Ok((layer, scope)),
+        None => Err(not_ancestor_error::<S, X>()),
+    }
+}
+
+/// Finds the `X::Scope` layer that references to `X` IDs are resolved in.
+///
+/// This is the nearest `X::Scope` layer,
+/// except when `X` is its own scope,
+/// where references in an `X` resolve among its siblings
+/// (i.e. in the same namespace as its own ID) instead of its children.
+fn reference_layer<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &<S as Schema>::Context,
+) -> Option<usize> {
+    let scope = TypeId::of::<X::Scope>();
+    let layer = context.find_layer(scope, LayerSelector::Nearest)?;
+    if scope == TypeId::of::<X>() {
+        context.find_layer(scope, LayerSelector::Below(layer))
+    } else {
+        Some(layer)
+    }
+}
+
+fn not_ancestor_error<S: Schema, X: KeyedIdentifiable<S>>() -> <S as Schema>::Error {
+    S::Error::new(format_args!(
+        "Scope {} is not an ancestor of {}",
+        type_name::<X::Scope>(),
+        type_name::<X>()
+    ))
+}
+
+fn too_many_error<S: Schema, X: KeyedIdentifiable<S>>() -> <S as Schema>::Error {
+    S::Error::new(format_args!("Too many {} IDs", type_name::<X>()))
+}
+
+/// Registers `index` as the current ID of the `X` layer at the index.
+fn declare_current<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+    layer: usize,
+    index: usize,
+    key: &X::Key,
+) -> Result<(), <S as Schema>::Error> {
+    let mut new = false;
+    let current_id = context
+        .try_get_mut_journaled_at::<CurrentId, _>(layer, || {
+            new = true;
+            CurrentId { id: index, parent: TypeId::of::<X::Scope>() }
+        })
+        .expect("layer was found by the caller");
+    if !new {
+        return Err(S::Error::new(format_args!(
+            "Multiple new IDs defined for {} ({}, {})",
+            type_name::<X>(),
+            index,
+            current_id.id,
+        )));
+    }
+
+    context.try_get_mut_journaled_at::<CurrentKey<X::Key>, _>(layer, || CurrentKey {
+        key: key.clone(),
+    });
+    Ok(())
+}
+
+/// Returns the ID path of the object converted in the layer at the index,
+/// i.e. the indices of the current IDs of the object and each of its ancestors,
+/// starting from the root.
+///
+/// The ancestors are found by walking down the stack,
+/// so recursive scope types are supported.
+fn parent_path(context: &impl Context, layer: usize) -> Vec<usize> {
+    let mut parent_ids = Vec::new();
+
+    let mut next_layer = Some(layer);
+    while let Some(layer) = next_layer {
+        let parent_id = match context.get_at::<CurrentId>(layer) {
+            Some(parent_id) => parent_id,
+            None => break,
+        };
+        parent_ids.push(parent_id.id);
+        next_layer = context.find_layer(parent_id.parent, LayerSelector::Below(layer));
     }
+
+    parent_ids.reverse();
+    parent_ids
 }
 
 /// Arguments for [`Id`].
@@ -225,8 +708,9 @@ pub struct IdArgs {
     /// The identifier will persist with respect to the unique identifier of the parent.
     /// containing the identifiers of all ancestors (i.e. `X::Scope`, `X::Scope::Scope`, etc.).
     ///
-    /// This option is only valid when `new` is `true`,
-    /// and cannot be used if the type recurses.
+    /// This option is only valid when `new` is `true`.
+    /// If `X::Scope` recurses,
+    /// the path contains the IDs of each enclosing `X::Scope` object.
     pub track: bool,
 
     /// Import identifiers whose scope is the object referenced by this identifier.
@@ -284,31 +768,60 @@ pub struct IdArgs {
     /// Then `Foo::qux` will be resolved using `Foo::bar` as the scope.
     /// This imported scope lasts for the rest of the scope of the object declaring this ID,
     /// i.e. during the conversion of the fields in `Foo` behind `Foo::bar`.
+    ///
+    /// Imports can be chained for deeper hierarchies:
+    /// if `Qux` were the scope of another type,
+    /// `Foo::qux` could import that type in turn,
+    /// which would then be resolved in the `Qux` under the imported `Bar`.
     pub import: Vec<TypeId>,
+
+    /// The separator of qualified references.
+    ///
+    /// If set, a reference containing the separator is resolved as a qualified ID,
+    /// i.e. the names of the scope objects of the ID from the root,
+    /// followed by the name of the ID,
+    /// e.g. `bar_one/qux_three` with the separator `/` for the example in [`IdArgs::import`].
+    /// Each scoped ID in the path must be declared with `track = true`.
+    /// The segments are parsed with [`KeyedIdentifiable::parse_key`]
+    /// of the type they refer to.
+    /// References without the separator are resolved normally.
+    ///
+    /// This option is only valid when `new` is `false`.
+    /// Since the scopes of qualified references are not recorded,
+    /// references with a separator cannot be unconverted.
+    pub separator: Option<&'static str>,
 }
 
-/// Retrieves the original string ID for an identifiable object.
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct IdString<S, X> {
-    value: String,
+/// Retrieves the original key of the ID for an identifiable object,
+/// i.e. the original string for [`Identifiable`] types.
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(serialize = "X::Key: Serialize", deserialize = "X::Key: Deserialize<'de>"))
+)]
+pub struct IdString<S: Schema, X: KeyedIdentifiable<S>> {
+    value: X::Key,
     _ph:   PhantomData<fn() -> (S, X)>,
 }
 
-impl<S, X> IdString<S, X> {
-    pub fn value(&self) -> &str { &self.value }
+impl<S: Schema, X: KeyedIdentifiable<S>> IdString<S, X> {
+    pub fn value(&self) -> &X::Key { &self.value }
 }
 
-impl<S, X> fmt::Debug for IdString<S, X> {
+impl<S: Schema, X: KeyedIdentifiable<S>> fmt::Debug for IdString<S, X>
+where
+    X::Key: fmt::Debug,
+{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("IdString").field("value", &self.value).finish()
     }
 }
 
-impl<S, X> Clone for IdString<S, X> {
+impl<S: Schema, X: KeyedIdentifiable<S>> Clone for IdString<S, X> {
     fn clone(&self) -> Self { Self { value: self.value.clone(), _ph: PhantomData } }
 }
 
-impl<S: Schema, X: Identifiable<S>> Xylem<S> for IdString<S, X> {
+impl<S: Schema, X: KeyedIdentifiable<S>> Xylem<S> for IdString<S, X> {
     type From = ();
     type Args = NoArgs;
 
@@ -318,31 +831,751 @@ impl<S: Schema, X: Identifiable<S>> Xylem<S> for IdString<S, X> {
         context: &mut <S as Schema>::Context,
         _args: &Self::Args,
     ) -> Result<Self, <S as Schema>::Error> {
-        let id = match context.get::<CurrentId>(TypeId::of::<X>()) {
-            Some(id) => id,
-            None => {
-                return Err(S::Error::new(format_args!("No current ID for {}", type_name::<X>())))
+        match context.get::<CurrentKey<X::Key>>(TypeId::of::<X>()) {
+            Some(current) => Ok(Self { value: current.key.clone(), _ph: PhantomData }),
+            None => Err(S::Error::new(format_args!("No current ID for {}", type_name::<X>()))),
+        }
+    }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> XylemInverse<S> for IdString<S, X> {
+    #[inline]
+    fn unconvert_impl(
+        self,
+        _context: &mut <S as Schema>::Context,
+        _args: &Self::Args,
+    ) -> Result<Self::From, <S as Schema>::Error> {
+        Ok(())
+    }
+}
+
+/// Registers alternative names for the ID of an identifiable object,
+/// e.g. the old names of renamed objects.
+///
+/// The field must be converted after the [`Id`] field declared with `new = true`.
+/// References to an alias resolve to the same index as the declared name
+/// and report a deprecation warning through [`Context::warn`]
+/// with the alias and the declared name.
+/// Aliases share the namespace of the declared names,
+/// so an alias cannot be the same as another name or alias in the scope.
+///
+/// Aliases are only resolved in the namespace they are declared in,
+/// i.e. not through imported scopes.
+///
+/// # Example
+/// ```
+/// use xylem::{Context, DefaultContext, Id, IdAliases, Identifiable, NoArgs, Xylem};
+///
+/// xylem::declare_schema!(Schema: xylem::SchemaExt);
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Schema, expose = ItemFrom)]
+/// struct Item {
+///     #[xylem(args(new = true))]
+///     id:      Id<Schema, Item>,
+///     aliases: IdAliases<Schema, Item>,
+/// }
+///
+/// impl Identifiable<Schema> for Item {
+///     type Scope = ();
+///     fn id(&self) -> Id<Schema, Item> { self.id }
+/// }
+///
+/// let mut context = DefaultContext::default();
+/// let item = ItemFrom { id: String::from("sword"), aliases: vec![String::from("blade")] };
+/// let item = Item::convert(item, &mut context, &NoArgs).unwrap();
+///
+/// let id = Id::<Schema, Item>::convert(String::from("blade"), &mut context, &Default::default());
+/// assert_eq!(id.unwrap(), item.id);
+/// assert_eq!(context.warnings()[0].message(), "ID blade is a deprecated alias of sword");
+/// ```
+pub struct IdAliases<S: Schema, X: KeyedIdentifiable<S>> {
+    names: Vec<X::Key>,
+    _ph:   PhantomData<fn() -> (S, X)>,
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> IdAliases<S, X> {
+    /// Returns the aliases.
+    pub fn names(&self) -> &[X::Key] { &self.names }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> fmt::Debug for IdAliases<S, X>
+where
+    X::Key: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IdAliases").field("names", &self.names).finish()
+    }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> Clone for IdAliases<S, X> {
+    fn clone(&self) -> Self { Self { names: self.names.clone(), _ph: PhantomData } }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> Xylem<S> for IdAliases<S, X> {
+    type From = Vec<X::Key>;
+    type Args = NoArgs;
+
+    #[inline]
+    fn convert_impl(
+        from: Self::From,
+        context: &mut <S as Schema>::Context,
+        _args: &Self::Args,
+    ) -> Result<Self, <S as Schema>::Error> {
+        register_aliases::<S, X>(context, &from, false)?;
+        Ok(Self { names: from, _ph: PhantomData })
+    }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> XylemInverse<S> for IdAliases<S, X> {
+    #[inline]
+    fn unconvert_impl(
+        self,
+        context: &mut <S as Schema>::Context,
+        _args: &Self::Args,
+    ) -> Result<Self::From, <S as Schema>::Error> {
+        register_aliases::<S, X>(context, &self.names, true)?;
+        Ok(self.names)
+    }
+}
+
+/// Registers aliases for the current `X` ID.
+///
+/// If `restore` is `true`, aliases already registered for the same ID are accepted,
+/// so that values can be unconverted with the context of their conversion.
+fn register_aliases<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+    aliases: &[X::Key],
+    restore: bool,
+) -> Result<(), <S as Schema>::Error> {
+    let (layer, scope) = declaring_layers::<S, X>(context)?;
+    let index = match context.get_at::<CurrentId>(layer) {
+        Some(current) => current.id,
+        None => {
+            return Err(S::Error::new(format_args!(
+                "{} aliases must be converted after the new ID",
+                type_name::<X>()
+            )))
+        }
+    };
+
+    let span = context.span();
+    for alias in aliases {
+        let counter = counter_at::<S, X>(context, scope);
+        match counter.resolve(alias) {
+            Some((other, _)) if restore && other == index => continue,
+            Some((other, _)) => {
+                let first = counter.span(other);
+                return Err(S::Error::new(format_args!(
+                    "Duplicate ID {}{}",
+                    alias,
+                    duplicate_spans(&first, &span),
+                ))
+                .with_spans(known_spans([first, span])));
             }
+            None => {}
+        }
+
+        context.journal_undo_at(scope, |_: &IdCounter<S, X>| {
+            let alias = alias.clone();
+            move |counter: &mut IdCounter<S, X>| {
+                counter.aliases.remove(&alias);
+            }
+        });
+        counter_at::<S, X>(context, scope).aliases.insert(alias.clone(), index);
+    }
+
+    Ok(())
+}
+
+/// A reference to an identifier for type `X`
+/// that may be declared after the reference.
+///
+/// If the name is not declared yet during conversion,
+/// it is resolved when the `X::Scope` scope it refers to ends,
+/// or when [`finalize`](crate::finalize) is called if `X::Scope` is `()`.
+/// The conversion of `X::Scope` fails with an "Unknown ID" error
+/// annotated with the path of the reference
+/// if the name is still not declared by then.
+/// Global references made by a top-level conversion that fails
+/// are discarded instead of being resolved by [`finalize`](crate::finalize).
+///
+/// Unlike [`Id`], `LazyId` does not accept [`IdArgs`],
+/// so it cannot declare new IDs or import scopes.
+pub struct LazyId<S, X> {
+    cell: Arc<OnceLock<Id<S, X>>>,
+}
+
+impl<S, X> LazyId<S, X> {
+    /// Creates a reference that is already resolved.
+    pub fn resolved(id: Id<S, X>) -> Self {
+        let cell = OnceLock::new();
+        let _ = cell.set(id);
+        Self { cell: Arc::new(cell) }
+    }
+
+    /// Returns the identifier, or `None` if it is not resolved yet.
+    pub fn get(&self) -> Option<Id<S, X>> { self.cell.get().copied() }
+
+    /// Returns the identifier.
+    ///
+    /// # Panics
+    /// Panics if the identifier is not resolved yet,
+    /// which does not happen after the conversion of `X::Scope` has succeeded.
+    pub fn id(&self) -> Id<S, X> { self.get().expect("LazyId is not resolved yet") }
+}
+
+impl<S, X> fmt::Debug for LazyId<S, X> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.get() {
+            Some(id) => write!(f, "LazyId({})", id.index),
+            None => write!(f, "LazyId(?)"),
+        }
+    }
+}
+
+impl<S, X> Clone for LazyId<S, X> {
+    fn clone(&self) -> Self { Self { cell: Arc::clone(&self.cell) } }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> Xylem<S> for LazyId<S, X> {
+    type From = X::Key;
+    type Args = NoArgs;
+
+    #[inline]
+    fn convert_impl(
+        from: Self::From,
+        context: &mut <S as Schema>::Context,
+        _args: &Self::Args,
+    ) -> Result<Self, <S as Schema>::Error> {
+        let scope = match reference_layer::<S, X>(context) {
+            Some(scope) => scope,
+            None => return Err(not_ancestor_error::<S, X>()),
         };
 
-        Ok(Self { value: id.string.clone(), _ph: PhantomData })
+        let index =
+            context.get_at::<IdCounter<S, X>>(scope).and_then(|counter| counter.resolve(&from));
+        if let Some((index, alias_of)) = index {
+            if let Some(name) = alias_of {
+                let path = context.path();
+                warn_alias::<S, _>(context, path, &from, &name);
+            }
+            if X::report_unused() {
+                let parent_ids = parent_path(context, scope);
+                record_reference::<S, X>(context, &parent_ids, index);
+            }
+            return Ok(Self::resolved(Id::new(index)));
+        }
+
+        let lazy = Self { cell: Arc::new(OnceLock::new()) };
+        let pending = PendingId {
+            name: from,
+            lazy: lazy.clone(),
+            path: context.path(),
+            span: context.span(),
+        };
+        defer_at::<S>(
+            context,
+            scope,
+            Rc::new(move |context| resolve_pending::<S, X>(context, scope, &pending)),
+        );
+
+        Ok(lazy)
+    }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> XylemInverse<S> for LazyId<S, X> {
+    #[inline]
+    fn unconvert_impl(
+        self,
+        context: &mut <S as Schema>::Context,
+        _args: &Self::Args,
+    ) -> Result<Self::From, <S as Schema>::Error> {
+        match self.get() {
+            Some(id) => id.unconvert_impl(context, &IdArgs::default()),
+            None => Err(S::Error::new(format_args!("Unresolved {} ID", type_name::<X>()))),
+        }
+    }
+}
+
+/// Resolves a pending [`LazyId`] when the `X::Scope` layer at the index ends.
+fn resolve_pending<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+    scope: usize,
+    pending: &PendingId<S, X>,
+) -> Result<(), <S as Schema>::Error> {
+    let PendingId { name, lazy, path, span } = pending;
+    let index = context.get_at::<IdCounter<S, X>>(scope).and_then(|counter| counter.resolve(name));
+    let (index, alias_of) = match index {
+        Some(index) => index,
+        None => {
+            return Err(S::Error::new(format_args!("Unknown ID {}{}", name, reference_span(span)))
+                .with_spans(known_spans([span.clone()]))
+                .with_path(path.clone()))
+        }
+    };
+
+    if let Some(canonical) = alias_of {
+        warn_alias::<S, _>(context, path.clone(), name, &canonical);
+    }
+    if X::report_unused() {
+        let parent_ids = parent_path(context, scope);
+        record_reference::<S, X>(context, &parent_ids, index);
+    }
+    let _ = lazy.cell.set(Id::new(index));
+    Ok(())
+}
+
+/// Counts the references to the declared `X` IDs,
+/// keyed by the ID path of their scope and their index.
+///
+/// Each change is journaled with [`Context::journal_undo_at`],
+/// so that declarations and references after a rolled back checkpoint are discarded
+/// without cloning the whole usage at every checkpoint.
+struct IdUsage<X: 'static> {
+    scopes:    BTreeMap<Vec<usize>, BTreeMap<usize, IdUsageEntry>>,
+    /// Whether the report is registered in the root finalizers.
+    scheduled: bool,
+    _ph:       PhantomData<&'static X>,
+}
+
+#[derive(Clone, Default)]
+struct IdUsageEntry {
+    /// The name and the path of the declaration,
+    /// or `None` if the index was referenced through an import before it was recorded.
+    declaration: Option<(String, FieldPath)>,
+    references:  usize,
+    /// Whether the entry was reported as unused.
+    reported:    bool,
+}
+
+impl<X: 'static> Default for IdUsage<X> {
+    fn default() -> Self { Self { scopes: BTreeMap::new(), scheduled: false, _ph: PhantomData } }
+}
+
+impl<X: 'static> IdUsage<X> {
+    fn entry(&mut self, parent: &[usize], index: usize) -> &mut IdUsageEntry {
+        if !self.scopes.contains_key(parent) {
+            self.scopes.insert(parent.to_vec(), BTreeMap::new());
+        }
+        self.scopes.get_mut(parent).expect("inserted above").entry(index).or_default()
+    }
+
+    /// Returns an undo entry that restores the entry of an index.
+    fn undo_entry(&self, parent: &[usize], index: usize) -> impl FnOnce(&mut Self) + 'static {
+        let parent = parent.to_vec();
+        let previous = self.scopes.get(&parent).and_then(|entries| entries.get(&index)).cloned();
+        move |usage: &mut Self| match previous {
+            Some(previous) => *usage.entry(&parent, index) = previous,
+            None => {
+                if let Some(entries) = usage.scopes.get_mut(&parent) {
+                    entries.remove(&index);
+                }
+            }
+        }
+    }
+
+    /// Returns an undo entry that restores whether the report is scheduled.
+    fn undo_schedule(&self) -> impl FnOnce(&mut Self) + 'static {
+        let scheduled = self.scheduled;
+        move |usage: &mut Self| usage.scheduled = scheduled
+    }
+}
+
+/// Returns the `X` usage, which is stored in the root layer.
+fn root_usage<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+) -> &mut IdUsage<X> {
+    context.try_get_mut_at::<IdUsage<X>, _>(0, Default::default).expect("root layer exists")
+}
+
+/// Records the declaration of an `X` ID for [`Identifiable::report_unused`].
+fn record_declaration<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+    parent: &[usize],
+    index: usize,
+    name: &X::Key,
+) {
+    if !X::report_unused() {
+        return;
+    }
+
+    let declaration = Some((name.to_string(), context.path()));
+    context.journal_undo_at(0, |usage: &IdUsage<X>| usage.undo_entry(parent, index));
+    root_usage::<S, X>(context).entry(parent, index).declaration = declaration;
+
+    if !root_usage::<S, X>(context).scheduled {
+        context.journal_undo_at(0, IdUsage::<X>::undo_schedule);
+        root_usage::<S, X>(context).scheduled = true;
+        defer_retained::<S>(context, Rc::new(schedule_unused_report::<S, X>));
+    }
+}
+
+/// Records a reference to an `X` ID for [`Identifiable::report_unused`].
+fn record_reference<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+    parent: &[usize],
+    index: usize,
+) {
+    if X::report_unused() {
+        context.journal_undo_at(0, |usage: &IdUsage<X>| usage.undo_entry(parent, index));
+        root_usage::<S, X>(context).entry(parent, index).references += 1;
+    }
+}
+
+/// Defers the report of unused `X` IDs once more,
+/// so that it runs after the other root finalizers,
+/// which may resolve references to the IDs.
+fn schedule_unused_report<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+) -> Result<(), <S as Schema>::Error> {
+    defer_at::<S>(context, 0, Rc::new(report_unused::<S, X>));
+    Ok(())
+}
+
+/// Warns about the declared `X` IDs that are not referenced.
+fn report_unused<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+) -> Result<(), <S as Schema>::Error> {
+    context.journal_undo_at(0, IdUsage::<X>::undo_schedule);
+    let usage = root_usage::<S, X>(context);
+    usage.scheduled = false;
+
+    let mut warnings = Vec::new();
+    for entry in usage.scopes.values_mut().flat_map(BTreeMap::values_mut) {
+        if let (Some((name, path)), 0, false) =
+            (&entry.declaration, entry.references, entry.reported)
+        {
+            entry.reported = true;
+            warnings.push(Warning::new(path.clone(), format!("Unused ID {}", name)));
+        }
     }
+
+    diagnostics::report(context, warnings);
+    Ok(())
+}
+
+/// A [`LazyId`] that is not resolved yet.
+struct PendingId<S: Schema, X: KeyedIdentifiable<S>> {
+    name: X::Key,
+    lazy: LazyId<S, X>,
+    /// The path of the reference.
+    path: FieldPath,
+    /// The source span of the reference, if known.
+    span: Option<Range<usize>>,
 }
 
 /// Tracks the list of IDs in a scope.
-struct IdCounter<X: 'static> {
-    names: Vec<String>,
-    _ph:   PhantomData<&'static X>,
+///
+/// The keys are stored in declaration order.
+/// The index of each ID is its position in the list,
+/// unless the indices are hashed.
+struct IdCounter<S: Schema, X: KeyedIdentifiable<S>> {
+    names:    IdNames<X::Key>,
+    /// The source spans of the declarations, if known.
+    spans:    Vec<Option<Range<usize>>>,
+    /// Whether each key has been declared.
+    /// Keys reserved by a seeded [`IdTable`] are not declared until they occur.
+    declared: Vec<bool>,
+    /// The indices of the keys registered with [`IdAliases`].
+    aliases:  HashMap<X::Key, usize>,
+    indexing: IdIndexing,
+    /// The position of each hashed index in `names`.
+    hashes:   HashMap<usize, usize>,
+    _ph:      PhantomData<&'static (S, X)>,
 }
 
-impl<X: 'static> IdCounter<X> {}
+impl<S: Schema, X: KeyedIdentifiable<S>> IdCounter<S, X> {
+    /// Creates a counter with the keys reserved by a seeded [`IdTable`].
+    fn reserved(names: Vec<Option<X::Key>>, indexing: IdIndexing) -> Self {
+        let mut counter = Self {
+            names: IdNames::default(),
+            spans: Vec::new(),
+            declared: Vec::new(),
+            aliases: HashMap::new(),
+            indexing,
+            hashes: HashMap::new(),
+            _ph: PhantomData,
+        };
+        for name in names {
+            counter.push(name, false, None);
+        }
+        counter
+    }
+
+    /// Returns the position of an index in `names`.
+    fn position_of(&self, index: usize) -> Option<usize> {
+        match self.indexing {
+            IdIndexing::Sequential => Some(index),
+            IdIndexing::Hashed => self.hashes.get(&index).copied(),
+        }
+    }
+
+    /// Returns the index of a declared key.
+    fn lookup(&self, name: &X::Key) -> Option<usize> {
+        let position = self.names.position(name).filter(|&position| self.declared[position])?;
+        Some(self.indexing.index(name, position))
+    }
+
+    /// Returns the index of a declared key or alias,
+    /// and the declared key if `name` is an alias.
+    fn resolve(&self, name: &X::Key) -> Option<(usize, Option<X::Key>)> {
+        if let Some(index) = self.lookup(name) {
+            return Some((index, None));
+        }
+        let &index = self.aliases.get(name)?;
+        Some((index, self.name(index).cloned()))
+    }
+
+    /// Returns the key of a declared index.
+    fn name(&self, index: usize) -> Option<&X::Key> { self.name_at(self.position_of(index)?) }
+
+    /// Returns the key declared at a position in `names`.
+    fn name_at(&self, position: usize) -> Option<&X::Key> {
+        match self.declared.get(position) {
+            Some(true) => self.names.name(position),
+            _ => None,
+        }
+    }
+
+    /// Returns the source span of the declaration of an index, if known.
+    fn span(&self, index: usize) -> Option<Range<usize>> {
+        self.spans.get(self.position_of(index)?)?.clone()
+    }
+
+    /// Returns the index that `name` would be declared at.
+    fn next_index(&self, name: &X::Key) -> usize {
+        match self.indexing {
+            IdIndexing::Sequential => self.names.position(name).unwrap_or(self.names.len()),
+            IdIndexing::Hashed => hash_key(name) as usize,
+        }
+    }
 
-impl<X: 'static> Default for IdCounter<X> {
-    fn default() -> Self { Self { names: Vec::new(), _ph: PhantomData } }
+    /// Returns another key with the same hashed index as `name`, if any.
+    fn collision(&self, name: &X::Key) -> Option<&X::Key> {
+        if self.indexing == IdIndexing::Sequential {
+            return None;
+        }
+        let &position = self.hashes.get(&(hash_key(name) as usize))?;
+        self.names.name(position).filter(|&other| other != name)
+    }
+
+    /// Declares a key that is not declared yet,
+    /// reusing its position if it was reserved.
+    ///
+    /// Returns the position of the key in `names`.
+    fn declare(&mut self, name: X::Key, span: Option<Range<usize>>) -> usize {
+        match self.names.position(&name) {
+            Some(position) => {
+                self.spans[position] = span;
+                self.declared[position] = true;
+                position
+            }
+            None => self.push(Some(name), true, span),
+        }
+    }
+
+    /// Appends a key restored from a [`GlobalIdStore`],
+    /// where reserved indices that were not declared have no key.
+    fn restore(&mut self, name: Option<X::Key>) {
+        let declared = name.is_some();
+        self.push(name, declared, None);
+    }
+
+    /// Appends a key, or an index without a key if `name` is `None`,
+    /// and returns its position in `names`.
+    fn push(&mut self, name: Option<X::Key>, declared: bool, span: Option<Range<usize>>) -> usize {
+        let position = self.names.len();
+        if let (IdIndexing::Hashed, Some(name)) = (self.indexing, &name) {
+            self.hashes.entry(hash_key(name) as usize).or_insert(position);
+        }
+        self.names.push(name);
+        self.spans.push(span);
+        self.declared.push(declared);
+        position
+    }
+
+    /// Removes the keys at and after the position `len` in `names`.
+    fn truncate(&mut self, len: usize) {
+        if self.indexing == IdIndexing::Hashed {
+            for position in len..self.names.len() {
+                if let Some(name) = self.names.name(position) {
+                    let hash = hash_key(name) as usize;
+                    if self.hashes.get(&hash) == Some(&position) {
+                        self.hashes.remove(&hash);
+                    }
+                }
+            }
+        }
+        self.names.truncate(len);
+        self.spans.truncate(len);
+        self.declared.truncate(len);
+    }
+
+    /// Returns the undo entry of [`IdCounter::declare`] for [`Context::journal_undo_at`].
+    fn undo_declare(&self, name: &X::Key) -> impl FnOnce(&mut Self) + 'static {
+        let len = self.names.len();
+        let reserved = self.names.position(name);
+        move |counter| {
+            counter.truncate(len);
+            if let Some(position) = reserved {
+                counter.spans[position] = None;
+                counter.declared[position] = false;
+            }
+        }
+    }
+}
+
+/// A list of ID keys ordered by index,
+/// with a hash index to look up the index of a key in constant time.
+///
+/// Indices without a key are `None`,
+/// e.g. indices reserved by an [`IdTable`] that was recorded from another table.
+/// Dereferences to the slice of keys.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(
+        from = "Vec<Option<K>>",
+        into = "Vec<Option<K>>",
+        bound(serialize = "K: IdKey + Serialize", deserialize = "K: IdKey + Deserialize<'de>")
+    )
+)]
+pub struct IdNames<K = String> {
+    names:   Vec<Option<K>>,
+    /// The index of each key.
+    indices: HashMap<K, usize>,
+}
+
+impl<K: IdKey> IdNames<K> {
+    /// Returns the index of a key.
+    pub fn position<Q: Eq + Hash + ?Sized>(&self, name: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.indices.get(name).copied()
+    }
+
+    /// Returns the key at an index, or `None` if the index has no key.
+    pub fn name(&self, index: usize) -> Option<&K> { self.names.get(index)?.as_ref() }
+
+    /// Returns the keys as a slice.
+    pub fn as_slice(&self) -> &[Option<K>] { &self.names }
+
+    /// Sets the key at an index,
+    /// filling the indices before it with indices without a key if necessary.
+    ///
+    /// If the key is also at another index,
+    /// lookups of the key return the first index.
+    pub fn set(&mut self, index: usize, name: K) { self.replace(index, Some(name)); }
+
+    /// Appends an index, which has no key if `name` is `None`.
+    pub fn push(&mut self, name: Option<K>) { self.replace(self.names.len(), name); }
+
+    /// Removes the indices at and after `len`.
+    fn truncate(&mut self, len: usize) {
+        if len >= self.names.len() {
+            return;
+        }
+        for (index, name) in self.names.drain(len..).enumerate() {
+            if let Some(name) = name {
+                if self.indices.get(&name) == Some(&(len + index)) {
+                    self.indices.remove(&name);
+                }
+            }
+        }
+    }
+
+    fn replace(&mut self, index: usize, name: Option<K>) {
+        if self.names.len() <= index {
+            self.names.resize(index + 1, None);
+        }
+        if self.names[index] == name {
+            return;
+        }
+
+        if let Some(old) = mem::replace(&mut self.names[index], name) {
+            if self.indices.get(&old) == Some(&index) {
+                self.indices.remove(&old);
+                if let Some(other) = self.names.iter().position(|name| name.as_ref() == Some(&old))
+                {
+                    self.indices.insert(old, other);
+                }
+            }
+        }
+
+        if let Some(name) = &self.names[index] {
+            match self.indices.get_mut(name) {
+                Some(other) => *other = (*other).min(index),
+                None => {
+                    self.indices.insert(name.clone(), index);
+                }
+            }
+        }
+    }
+}
+
+impl<K> Default for IdNames<K> {
+    fn default() -> Self { Self { names: Vec::new(), indices: HashMap::new() } }
+}
+
+impl<K: PartialEq> PartialEq for IdNames<K> {
+    fn eq(&self, other: &Self) -> bool { self.names == other.names }
+}
+
+impl<K: Eq> Eq for IdNames<K> {}
+
+impl<K> Deref for IdNames<K> {
+    type Target = [Option<K>];
+
+    fn deref(&self) -> &[Option<K>] { &self.names }
+}
+
+impl<K: IdKey> FromIterator<Option<K>> for IdNames<K> {
+    fn from_iter<I: IntoIterator<Item = Option<K>>>(iter: I) -> Self {
+        let mut names = Self::default();
+        for name in iter {
+            names.push(name);
+        }
+        names
+    }
+}
+
+impl<K: IdKey> From<Vec<Option<K>>> for IdNames<K> {
+    fn from(names: Vec<Option<K>>) -> Self { names.into_iter().collect() }
+}
+
+impl<K> From<IdNames<K>> for Vec<Option<K>> {
+    fn from(names: IdNames<K>) -> Self { names.names }
+}
+
+/// Returns the counter in the layer at the index,
+/// creating it with the keys reserved by a seeded [`IdTable`] if it does not exist.
+fn counter_at<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &mut <S as Schema>::Context,
+    scope: usize,
+) -> &mut IdCounter<S, X> {
+    if context.get_at::<IdCounter<S, X>>(scope).is_none() {
+        // the counter does not exist yet, so it is removed on rollback
+        context.journal_undo_at(scope, |_: &IdCounter<S, X>| |_: &mut IdCounter<S, X>| {});
+
+        if let Some(seeds) = context.get::<IdSeeds<S, X>>(TypeId::of::<()>()) {
+            if let Some(names) = seeds.scopes.get(&parent_path(context, scope)).cloned() {
+                context.try_get_mut_at(scope, || IdCounter::<S, X>::reserved(names, X::indexing()));
+            }
+        }
+    }
+
+    context
+        .try_get_mut_at::<IdCounter<S, X>, _>(scope, || {
+            IdCounter::reserved(Vec::new(), X::indexing())
+        })
+        .expect("layer was found by the caller")
 }
 
 /// Tracks the current ID.
-#[derive(Getters, CopyGetters)]
+#[derive(Clone, CopyGetters)]
 pub struct CurrentId {
     /// The index of the current identifier.
     ///
@@ -352,9 +1585,13 @@ pub struct CurrentId {
     /// The type ID of the parent.
     #[getset(get_copy = "pub")]
     parent: TypeId,
-    /// The original string ID.
-    #[getset(get = "pub")]
-    string: String,
+}
+
+/// Tracks the original key of the current ID for [`IdString`],
+/// stored in the same layer as its [`CurrentId`].
+#[derive(Clone)]
+struct CurrentKey<K> {
+    key: K,
 }
 
 /// Stores the globally tracked IDs.
@@ -362,32 +1599,472 @@ pub struct CurrentId {
 /// This is a low-level implementation.
 /// Prefer using [`IdArgs`] with `track` and `import` for a more simple and stable API.
 #[derive(Getters, MutGetters)]
-pub struct GlobalIdStore<S: Schema, X: Identifiable<S>> {
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(serialize = "X::Key: Serialize", deserialize = "X::Key: Deserialize<'de>"))
+)]
+pub struct GlobalIdStore<S: Schema, X: KeyedIdentifiable<S>> {
     /// The actual storage.
     ///
     /// The key is the ID path to the scope `X::Scope`,
-    /// and the value is a list of the IDs in that scope.
+    /// and the value is a list of the [`KeyedIdentifiable::Key`]s of the IDs in that scope.
+    /// Indices reserved by an [`IdTable`] but not declared have no key.
     #[getset(get = "pub", get_mut = "pub")]
-    ids: BTreeMap<Vec<usize>, Vec<String>>,
+    #[cfg_attr(feature = "serde", serde(with = "path_map"))]
+    ids: BTreeMap<Vec<usize>, IdNames<X::Key>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _ph: PhantomData<&'static (S, X)>,
 }
 
-impl<S: Schema, X: Identifiable<S>> Default for GlobalIdStore<S, X> {
+impl<S: Schema, X: KeyedIdentifiable<S>> Default for GlobalIdStore<S, X> {
     fn default() -> Self { Self { ids: BTreeMap::new(), _ph: PhantomData } }
 }
 
-#[derive(Default)]
+impl<S: Schema, X: KeyedIdentifiable<S>> Clone for GlobalIdStore<S, X> {
+    fn clone(&self) -> Self { Self { ids: self.ids.clone(), _ph: PhantomData } }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> GlobalIdStore<S, X> {
+    /// Returns the undo entry of setting the key at `position` in the scope with the ID path
+    /// `parent` for [`Context::journal_undo_at`].
+    fn undo_set(&self, parent: &[usize], position: usize) -> impl FnOnce(&mut Self) + 'static {
+        let previous =
+            self.ids.get(parent).map(|names| (names.len(), names.name(position).cloned()));
+        let parent = parent.to_vec();
+        move |store| match (previous, store.ids.get_mut(&parent)) {
+            (Some((len, name)), Some(names)) => {
+                if position < len {
+                    names.replace(position, name);
+                }
+                names.truncate(len);
+            }
+            (None, _) => {
+                store.ids.remove(&parent);
+            }
+            (Some(_), None) => {}
+        }
+    }
+}
+
+#[derive(Clone, Default)]
 struct ImportScope {
     map: BTreeMap<TypeId, Vec<usize>>,
 }
 
-/// A trait for types that can be identified.
+/// A saved table of ID names,
+/// used to keep the indices of IDs stable across conversions,
+/// e.g. between processes that convert the same config separately,
+/// or for save files that store ID indices.
+///
+/// After converting with a context,
+/// [`IdTable::record`] saves the IDs of a type from the context.
+/// Before converting with a new context,
+/// [`IdTable::seed`] reserves the saved indices in the context.
+/// IDs declared in the new conversion keep their saved index,
+/// and new IDs are appended after the saved ones.
+/// Saved IDs that are no longer declared become tombstones:
+/// their indices are not reused, and references to them are unknown IDs.
+///
+/// Global IDs (where `X::Scope` is `()`) are always recorded.
+/// Scoped IDs are only recorded if they are declared with `track = true`,
+/// and are saved with respect to the ID path of their scope.
+///
+/// Keys are saved in their display form,
+/// and are parsed back with [`KeyedIdentifiable::parse_key`] when the table is seeded.
+/// Seeding fails if a saved name cannot be parsed.
+///
+/// The table is serializable with the `serde` feature.
+///
+/// # Example
+/// ```
+/// use xylem::id::IdTable;
+/// use xylem::{DefaultContext, Id, Identifiable, NoArgs, Xylem};
+///
+/// xylem::declare_schema!(Schema: xylem::SchemaExt);
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Schema, expose = ZoneFrom)]
+/// struct Zone {
+///     #[xylem(args(new = true))]
+///     id: Id<Schema, Zone>,
+/// }
+///
+/// impl Identifiable<Schema> for Zone {
+///     type Scope = ();
+///     fn id(&self) -> Id<Schema, Zone> { self.id }
+/// }
+///
+/// let zones = |names: &[&str]| -> Vec<ZoneFrom> {
+///     names.iter().map(|name| ZoneFrom { id: name.to_string() }).collect()
+/// };
+///
+/// let mut context = DefaultContext::default();
+/// Vec::<Zone>::convert(zones(&["forest", "cave"]), &mut context, &NoArgs).unwrap();
+/// let mut table = IdTable::default();
+/// table.record::<Schema, Zone>(&context);
+///
+/// let mut context = DefaultContext::default();
+/// table.seed::<Schema, Zone>(&mut context).unwrap();
+/// let converted = Vec::<Zone>::convert(zones(&["lake", "cave"]), &mut context, &NoArgs).unwrap();
+/// assert_eq!(converted[0].id.index(), 2); // appended
+/// assert_eq!(converted[1].id.index(), 1); // kept
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IdTable {
+    /// The saved scopes of each type, keyed by [`Identifiable::table_key`].
+    types: BTreeMap<String, ScopeTables>,
+}
+
+/// The saved IDs of a type, keyed by the ID path of their scope.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+struct ScopeTables {
+    #[cfg_attr(feature = "serde", serde(with = "path_map"))]
+    scopes: BTreeMap<Vec<usize>, Vec<IdEntry>>,
+}
+
+/// A saved ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct IdEntry {
+    /// The name of the ID,
+    /// or `None` for an index reserved by another table, which cannot be named.
+    name:    Option<String>,
+    /// Whether the ID was not declared in the last recorded conversion.
+    removed: bool,
+}
+
+impl IdTable {
+    /// Records the `X` IDs in the context into the table.
+    ///
+    /// Indices already in the table are kept.
+    /// Saved IDs in the recorded scopes that were not declared become tombstones.
+    pub fn record<S: Schema, X: KeyedIdentifiable<S>>(&mut self, context: &<S as Schema>::Context) {
+        let recorded = recorded_names::<S, X>(context);
+
+        let tables = self.types.entry(X::table_key().to_string()).or_default();
+        for (parent, names) in recorded {
+            let entries = tables.scopes.entry(parent).or_default();
+            for (index, name) in names.iter().enumerate() {
+                match (name, entries.get_mut(index)) {
+                    (Some(name), Some(entry)) => {
+                        *entry = IdEntry { name: Some(name.to_string()), removed: false }
+                    }
+                    (Some(name), None) => {
+                        entries.push(IdEntry { name: Some(name.to_string()), removed: false })
+                    }
+                    (None, Some(entry)) => entry.removed = true,
+                    (None, None) => entries.push(IdEntry { name: None, removed: true }),
+                }
+            }
+            for entry in entries.iter_mut().skip(names.len()) {
+                entry.removed = true;
+            }
+        }
+    }
+
+    /// Reserves the saved `X` IDs in a new context.
+    ///
+    /// This should be called before converting any values with the context.
+    ///
+    /// Returns an error if a saved name cannot be parsed with [`KeyedIdentifiable::parse_key`].
+    pub fn seed<S: Schema, X: KeyedIdentifiable<S>>(
+        &self,
+        context: &mut <S as Schema>::Context,
+    ) -> Result<(), <S as Schema>::Error> {
+        let tables = match self.types.get(X::table_key()) {
+            Some(tables) => tables,
+            None => return Ok(()),
+        };
+
+        let mut scopes = BTreeMap::new();
+        for (parent, entries) in &tables.scopes {
+            let mut names = Vec::with_capacity(entries.len());
+            for entry in entries {
+                let name = match entry.name.as_deref() {
+                    Some(name) => name,
+                    None => {
+                        names.push(None);
+                        continue;
+                    }
+                };
+                match X::parse_key(name) {
+                    Some(key) => names.push(Some(key)),
+                    None => {
+                        return Err(S::Error::new(format_args!(
+                            "Cannot parse saved {} ID {:?}",
+                            X::table_key(),
+                            name,
+                        )))
+                    }
+                }
+            }
+            scopes.insert(parent.clone(), names);
+        }
+        context.get_mut::<IdSeeds<S, X>, _>(TypeId::of::<()>(), Default::default).scopes = scopes;
+        Ok(())
+    }
+
+    /// Returns the saved names of the `X` IDs in the scope with the ID path `parent`,
+    /// ordered by index.
+    ///
+    /// Tombstones are `None`.
+    pub fn names<S: Schema, X: KeyedIdentifiable<S>>(
+        &self,
+        parent: &[usize],
+    ) -> impl Iterator<Item = Option<&str>> + '_ {
+        self.types
+            .get(X::table_key())
+            .and_then(|tables| tables.scopes.get(parent))
+            .into_iter()
+            .flatten()
+            .map(|entry| entry.name.as_deref().filter(|_| !entry.removed))
+    }
+}
+
+/// Returns the keys of the `X` IDs that can be recorded from the context,
+/// keyed by the ID path of their scope,
+/// where reserved indices that were not declared are `None`.
+fn recorded_names<S: Schema, X: KeyedIdentifiable<S>>(
+    context: &<S as Schema>::Context,
+) -> BTreeMap<Vec<usize>, Vec<Option<&X::Key>>> {
+    let mut recorded = BTreeMap::new();
+
+    if let Some(store) = context.get::<GlobalIdStore<S, X>>(TypeId::of::<()>()) {
+        for (parent, names) in &store.ids {
+            recorded.insert(parent.clone(), names.iter().map(Option::as_ref).collect());
+        }
+    }
+    if let Some(counter) = context.get::<IdCounter<S, X>>(TypeId::of::<()>()) {
+        let names = (0..counter.names.len()).map(|position| counter.name_at(position));
+        recorded.insert(Vec::new(), names.collect());
+    }
+
+    recorded
+}
+
+/// The keys reserved by [`IdTable::seed`], keyed by the ID path of their scope.
+struct IdSeeds<S: Schema, X: KeyedIdentifiable<S>> {
+    scopes: BTreeMap<Vec<usize>, Vec<Option<X::Key>>>,
+    _ph:    PhantomData<&'static (S, X)>,
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> Default for IdSeeds<S, X> {
+    fn default() -> Self { Self { scopes: BTreeMap::new(), _ph: PhantomData } }
+}
+
+/// Serializes maps keyed by ID paths as a sequence of entries,
+/// since some formats only support string keys.
+#[cfg(feature = "serde")]
+mod path_map {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<V: Serialize, Ser: Serializer>(
+        map: &BTreeMap<Vec<usize>, V>,
+        serializer: Ser,
+    ) -> Result<Ser::Ok, Ser::Error> {
+        serializer.collect_seq(map)
+    }
+
+    pub fn deserialize<'de, V: Deserialize<'de>, De: Deserializer<'de>>(
+        deserializer: De,
+    ) -> Result<BTreeMap<Vec<usize>, V>, De::Error> {
+        Ok(Vec::<(Vec<usize>, V)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+/// How the indices of the IDs of an [`Identifiable`] type are assigned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdIndexing {
+    /// IDs are indexed in the order they are declared in their scope,
+    /// so moving a declaration changes the indices of the IDs after it.
+    /// Use an [`IdTable`] to keep the indices stable across conversions.
+    #[default]
+    Sequential,
+    /// The index of each ID is the 32-bit FNV-1a hash of the display form of its key,
+    /// so it does not depend on the declaration order or on other IDs.
+    ///
+    /// The indices are sparse,
+    /// so the IDs should be stored in an [`IdMap`] rather than an [`IdVec`].
+    /// Declaring two names with the same hash in a scope is an error.
+    Hashed,
+}
+
+impl IdIndexing {
+    /// Returns the index of the ID `name` at `position` in the declaration order.
+    fn index<K: fmt::Display + ?Sized>(self, name: &K, position: usize) -> usize {
+        match self {
+            Self::Sequential => position,
+            Self::Hashed => hash_key(name) as usize,
+        }
+    }
+
+    /// Returns the position of the ID at `index` in a list of keys in declaration order.
+    ///
+    /// Hashed indices are searched linearly.
+    fn position<K: fmt::Display>(self, names: &[Option<K>], index: usize) -> Option<usize> {
+        match self {
+            Self::Sequential => names.get(index)?.as_ref().map(|_| index),
+            Self::Hashed => names.iter().position(|name| {
+                name.as_ref().is_some_and(|name| hash_key(name) as usize == index)
+            }),
+        }
+    }
+}
+
+/// Computes the 32-bit FNV-1a hash of the display form of a key for [`IdIndexing::Hashed`].
+fn hash_key<K: fmt::Display + ?Sized>(key: &K) -> u32 {
+    struct Fnv(u32);
+
+    impl fmt::Write for Fnv {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 = s
+                .bytes()
+                .fold(self.0, |hash, byte| (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193));
+            Ok(())
+        }
+    }
+
+    let mut hasher = Fnv(0x811c_9dc5);
+    fmt::Write::write_fmt(&mut hasher, format_args!("{}", key)).expect("hashing cannot fail");
+    hasher.0
+}
+
+/// A trait for types that can be identified,
+/// where the IDs are written as strings.
+///
+/// This implements [`KeyedIdentifiable`] with `Key = String`.
+/// Implement [`KeyedIdentifiable`] directly
+/// if the IDs are written as other types, e.g. integers.
 pub trait Identifiable<S: Schema>: Xylem<S> {
     /// The scope of the identifier namespace.
     ///
     /// Use `()` for global identifiers.
+    ///
+    /// The scope may be `Self` for recursive types, e.g. folders containing folders.
+    /// The ID of each object is then declared among its siblings in the enclosing object,
+    /// and references inside an object are resolved among its siblings as well.
     type Scope: Xylem<S>;
 
     /// Returns the identifier for this instance.
     fn id(&self) -> Id<S, Self>;
+
+    /// The key of this type in an [`IdTable`].
+    ///
+    /// The default implementation uses [`type_name`],
+    /// which is not guaranteed to be stable across compiler versions.
+    /// Override this if the table is shared between different builds.
+    fn table_key() -> &'static str { type_name::<Self>() }
+
+    /// Whether to warn about IDs of this type that are never referenced.
+    ///
+    /// The references to each declared ID are counted,
+    /// and IDs without references are reported through [`Context::warn`]
+    /// at the path of their declaration when [`finalize`](crate::finalize) is called.
+    /// Override this to return `false` for types that are not expected to be referenced,
+    /// e.g. entry points that are only accessed by the application.
+    fn report_unused() -> bool { true }
+
+    /// How the indices of the IDs of this type are assigned.
+    ///
+    /// Override this to return [`IdIndexing::Hashed`]
+    /// if the indices should not change when declarations are moved,
+    /// e.g. when they are stored in save files or sent over the network.
+    fn indexing() -> IdIndexing { IdIndexing::Sequential }
 }
+
+/// A trait for types that can be identified,
+/// where the IDs are written as `Self::Key`.
+///
+/// Types with string IDs should implement [`Identifiable`] instead,
+/// which implements this trait with `Key = String`.
+/// The items other than `Key` are the same as in [`Identifiable`].
+///
+/// # Example
+/// ```
+/// use xylem::id::KeyedIdentifiable;
+/// use xylem::{DefaultContext, Id, NoArgs, Xylem};
+///
+/// xylem::declare_schema!(Schema: xylem::SchemaExt);
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Schema, expose = ItemFrom)]
+/// struct Item {
+///     #[xylem(args(new = true))]
+///     id: Id<Schema, Item>,
+/// }
+///
+/// impl KeyedIdentifiable<Schema> for Item {
+///     type Key = u64;
+///     type Scope = ();
+///     fn id(&self) -> Id<Schema, Item> { self.id }
+///     fn parse_key(name: &str) -> Option<u64> { name.parse().ok() }
+/// }
+///
+/// let mut context = DefaultContext::default();
+/// let item = Item::convert(ItemFrom { id: 1024 }, &mut context, &NoArgs).unwrap();
+/// let id = Id::<Schema, Item>::convert(1024, &mut context, &Default::default()).unwrap();
+/// assert_eq!(id, item.id);
+/// ```
+pub trait KeyedIdentifiable<S: Schema>: Xylem<S> {
+    /// The type that the IDs are written as.
+    type Key: IdKey;
+
+    /// See [`Identifiable::Scope`].
+    type Scope: Xylem<S>;
+
+    /// See [`Identifiable::id`].
+    fn id(&self) -> Id<S, Self>;
+
+    /// See [`Identifiable::table_key`].
+    fn table_key() -> &'static str { type_name::<Self>() }
+
+    /// See [`Identifiable::report_unused`].
+    fn report_unused() -> bool { true }
+
+    /// See [`Identifiable::indexing`].
+    fn indexing() -> IdIndexing { IdIndexing::Sequential }
+
+    /// Parses a key from its display form,
+    /// or returns `None` if it is not a valid key.
+    ///
+    /// This is used to resolve the segments of qualified references
+    /// (see [`IdArgs::separator`])
+    /// and to reserve the keys saved in an [`IdTable`].
+    /// Types whose keys have no parsable display form may always return `None`,
+    /// in which case qualified references to them are unknown IDs
+    /// and seeding an [`IdTable`] with saved names fails.
+    fn parse_key(name: &str) -> Option<Self::Key>;
+}
+
+impl<S: Schema, X: Identifiable<S>> KeyedIdentifiable<S> for X {
+    type Key = String;
+    type Scope = <X as Identifiable<S>>::Scope;
+
+    fn id(&self) -> Id<S, Self> { Identifiable::id(self) }
+
+    fn table_key() -> &'static str { <X as Identifiable<S>>::table_key() }
+
+    fn report_unused() -> bool { <X as Identifiable<S>>::report_unused() }
+
+    fn indexing() -> IdIndexing { <X as Identifiable<S>>::indexing() }
+
+    fn parse_key(name: &str) -> Option<String> { Some(name.to_string()) }
+}
+
+/// A type that IDs can be written as, see [`KeyedIdentifiable::Key`].
+///
+/// IDs are identified by their keys.
+/// The [`Display`](fmt::Display) form of the keys is used
+/// as the name of the ID in diagnostics and [`IdTable`]s,
+/// and as the input of [`IdIndexing::Hashed`].
+///
+/// This is implemented for all types with the required traits,
+/// e.g. `String`, integers and UUIDs.
+pub trait IdKey: Clone + Eq + Hash + fmt::Display + 'static {}
+
+impl<K: Clone + Eq + Hash + fmt::Display + 'static> IdKey for K {}
@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Id, IdIndexing, IdRegistry, KeyedIdentifiable};
+use crate::Schema;
+
+/// A dense list of values indexed by `Id<S, X>`,
+/// e.g. the runtime data for each declared `X`.
+///
+/// Types with [`IdIndexing::Hashed`](super::IdIndexing::Hashed) have sparse indices,
+/// so their values should be stored in an [`IdMap`] instead.
+///
+/// # Example
+/// ```
+/// use xylem::id::IdRegistry;
+/// use xylem::{DefaultContext, Id, IdVec, Identifiable, NoArgs, Xylem};
+///
+/// xylem::declare_schema!(Schema: xylem::SchemaExt);
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Schema, expose = ItemFrom)]
+/// struct Item {
+///     #[xylem(args(new = true))]
+///     id: Id<Schema, Item>,
+/// }
+///
+/// impl Identifiable<Schema> for Item {
+///     type Scope = ();
+///     fn id(&self) -> Id<Schema, Item> { self.id }
+/// }
+///
+/// let mut context = DefaultContext::default();
+/// let items = vec![ItemFrom { id: String::from("sword") }, ItemFrom { id: String::from("bow") }];
+/// let items = Vec::<Item>::convert(items, &mut context, &NoArgs).unwrap();
+///
+/// let registry = IdRegistry::<Schema, Item>::from_context(&context);
+/// let mut stock = IdVec::from_registry(&registry, |_, _| 0);
+/// stock[items[1].id] += 3;
+/// assert_eq!(stock.iter().map(|(_, &count)| count).collect::<Vec<_>>(), [0, 3]);
+/// ```
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(transparent, bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct IdVec<S, X, T> {
+    values: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    _ph:    PhantomData<fn() -> (S, X)>,
+}
+
+impl<S, X, T> IdVec<S, X, T> {
+    /// Creates an empty list.
+    pub fn new() -> Self { Self { values: Vec::new(), _ph: PhantomData } }
+
+    /// Creates an empty list with space for at least `capacity` values.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { values: Vec::with_capacity(capacity), _ph: PhantomData }
+    }
+
+    /// Appends a value and returns its ID.
+    ///
+    /// # Panics
+    /// Panics if the index does not fit in an [`Id`].
+    pub fn push(&mut self, value: T) -> Id<S, X> {
+        let id = Id::new(self.values.len());
+        self.values.push(value);
+        id
+    }
+
+    /// Returns the value of an ID, or `None` if it is out of bounds.
+    pub fn get(&self, id: Id<S, X>) -> Option<&T> { self.values.get(id.index()) }
+
+    /// Returns the mutable value of an ID, or `None` if it is out of bounds.
+    pub fn get_mut(&mut self, id: Id<S, X>) -> Option<&mut T> { self.values.get_mut(id.index()) }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize { self.values.len() }
+
+    /// Returns whether the list is empty.
+    pub fn is_empty(&self) -> bool { self.values.is_empty() }
+
+    /// Iterates over the IDs in index order.
+    pub fn ids(&self) -> impl Iterator<Item = Id<S, X>> { (0..self.values.len()).map(Id::new) }
+
+    /// Iterates over the IDs and the values in index order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<S, X>, &T)> + '_ {
+        self.values.iter().enumerate().map(|(index, value)| (Id::new(index), value))
+    }
+
+    /// Iterates over the IDs and the mutable values in index order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Id<S, X>, &mut T)> + '_ {
+        self.values.iter_mut().enumerate().map(|(index, value)| (Id::new(index), value))
+    }
+
+    /// Returns the values as a slice in index order.
+    pub fn as_slice(&self) -> &[T] { &self.values }
+
+    /// Returns the values as a vector in index order.
+    pub fn into_vec(self) -> Vec<T> { self.values }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>, T> IdVec<S, X, T> {
+    /// Creates a list with a value for each global `X` ID in the registry,
+    /// including the indices reserved by an [`IdTable`](super::IdTable),
+    /// which have no name.
+    ///
+    /// # Panics
+    /// Panics if `X` uses [`IdIndexing::Hashed`].
+    pub fn from_registry(
+        registry: &IdRegistry<S, X>,
+        f: impl FnMut(Id<S, X>, Option<&X::Key>) -> T,
+    ) -> Self {
+        Self::from_registry_in(registry, &[], f)
+    }
+
+    /// Creates a list with a value for each `X` ID
+    /// in the scope with the ID path `parent` in the registry.
+    ///
+    /// # Panics
+    /// Panics if `X` uses [`IdIndexing::Hashed`].
+    pub fn from_registry_in(
+        registry: &IdRegistry<S, X>,
+        parent: &[usize],
+        mut f: impl FnMut(Id<S, X>, Option<&X::Key>) -> T,
+    ) -> Self {
+        assert!(
+            X::indexing() == IdIndexing::Sequential,
+            "IdVec cannot hold hashed IDs, use IdMap instead"
+        );
+        let values = (0..registry.len_in(parent))
+            .map(|index| {
+                let id = Id::new(index);
+                f(id, registry.name_in(parent, id))
+            })
+            .collect();
+        Self { values, _ph: PhantomData }
+    }
+}
+
+impl<S, X, T> Index<Id<S, X>> for IdVec<S, X, T> {
+    type Output = T;
+
+    fn index(&self, id: Id<S, X>) -> &T { &self.values[id.index()] }
+}
+
+impl<S, X, T> IndexMut<Id<S, X>> for IdVec<S, X, T> {
+    fn index_mut(&mut self, id: Id<S, X>) -> &mut T { &mut self.values[id.index()] }
+}
+
+impl<S, X, T> FromIterator<T> for IdVec<S, X, T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self { values: iter.into_iter().collect(), _ph: PhantomData }
+    }
+}
+
+impl<S, X, T> From<Vec<T>> for IdVec<S, X, T> {
+    fn from(values: Vec<T>) -> Self { Self { values, _ph: PhantomData } }
+}
+
+impl<S, X, T> Extend<T> for IdVec<S, X, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) { self.values.extend(iter) }
+}
+
+impl<S, X, T> IntoIterator for IdVec<S, X, T> {
+    type Item = (Id<S, X>, T);
+    type IntoIter = std::iter::Map<
+        std::iter::Enumerate<std::vec::IntoIter<T>>,
+        fn((usize, T)) -> (Id<S, X>, T),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter().enumerate().map(|(index, value)| (Id::new(index), value))
+    }
+}
+
+// We need to manually implement these traits
+// to avoid bounds on the type parameters `S` and `X`.
+
+impl<S, X, T> Default for IdVec<S, X, T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<S, X, T: Clone> Clone for IdVec<S, X, T> {
+    fn clone(&self) -> Self { Self { values: self.values.clone(), _ph: PhantomData } }
+}
+
+impl<S, X, T: fmt::Debug> fmt::Debug for IdVec<S, X, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(&self.values).finish()
+    }
+}
+
+impl<S, X, T: PartialEq> PartialEq for IdVec<S, X, T> {
+    fn eq(&self, other: &Self) -> bool { self.values == other.values }
+}
+
+impl<S, X, T: Eq> Eq for IdVec<S, X, T> {}
+
+/// A sparse map of values keyed by `Id<S, X>`,
+/// e.g. the data for some of the declared `X`.
+///
+/// Iteration is in index order.
+///
+/// With the `serde` feature, the map is serialized as a sequence of ID and value pairs,
+/// since some formats only support string keys.
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(transparent, bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct IdMap<S, X, T> {
+    #[cfg_attr(feature = "serde", serde(with = "id_map"))]
+    values: BTreeMap<Id<S, X>, T>,
+}
+
+impl<S, X, T> IdMap<S, X, T> {
+    /// Creates an empty map.
+    pub fn new() -> Self { Self { values: BTreeMap::new() } }
+
+    /// Inserts a value and returns the previous value of the ID.
+    pub fn insert(&mut self, id: Id<S, X>, value: T) -> Option<T> { self.values.insert(id, value) }
+
+    /// Removes the value of an ID.
+    pub fn remove(&mut self, id: Id<S, X>) -> Option<T> { self.values.remove(&id) }
+
+    /// Returns the value of an ID.
+    pub fn get(&self, id: Id<S, X>) -> Option<&T> { self.values.get(&id) }
+
+    /// Returns the mutable value of an ID.
+    pub fn get_mut(&mut self, id: Id<S, X>) -> Option<&mut T> { self.values.get_mut(&id) }
+
+    /// Returns whether the map contains a value for the ID.
+    pub fn contains(&self, id: Id<S, X>) -> bool { self.values.contains_key(&id) }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize { self.values.len() }
+
+    /// Returns whether the map is empty.
+    pub fn is_empty(&self) -> bool { self.values.is_empty() }
+
+    /// Iterates over the IDs with a value in index order.
+    pub fn ids(&self) -> impl Iterator<Item = Id<S, X>> + '_ { self.values.keys().copied() }
+
+    /// Iterates over the IDs and the values in index order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<S, X>, &T)> + '_ {
+        self.values.iter().map(|(&id, value)| (id, value))
+    }
+
+    /// Iterates over the IDs and the mutable values in index order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Id<S, X>, &mut T)> + '_ {
+        self.values.iter_mut().map(|(&id, value)| (id, value))
+    }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>, T> IdMap<S, X, T> {
+    /// Creates a map with a value for each declared global `X` ID in the registry.
+    pub fn from_registry(
+        registry: &IdRegistry<S, X>,
+        f: impl FnMut(Id<S, X>, &X::Key) -> T,
+    ) -> Self {
+        Self::from_registry_in(registry, &[], f)
+    }
+
+    /// Creates a map with a value for each declared `X` ID
+    /// in the scope with the ID path `parent` in the registry.
+    pub fn from_registry_in(
+        registry: &IdRegistry<S, X>,
+        parent: &[usize],
+        mut f: impl FnMut(Id<S, X>, &X::Key) -> T,
+    ) -> Self {
+        registry.iter_in(parent).map(|(id, name)| (id, f(id, name))).collect()
+    }
+}
+
+/// # Panics
+/// Panics if the ID has no value.
+impl<S, X, T> Index<Id<S, X>> for IdMap<S, X, T> {
+    type Output = T;
+
+    fn index(&self, id: Id<S, X>) -> &T {
+        self.values.get(&id).expect("No value for the ID in IdMap")
+    }
+}
+
+/// # Panics
+/// Panics if the ID has no value.
+impl<S, X, T> IndexMut<Id<S, X>> for IdMap<S, X, T> {
+    fn index_mut(&mut self, id: Id<S, X>) -> &mut T {
+        self.values.get_mut(&id).expect("No value for the ID in IdMap")
+    }
+}
+
+impl<S, X, T> FromIterator<(Id<S, X>, T)> for IdMap<S, X, T> {
+    fn from_iter<I: IntoIterator<Item = (Id<S, X>, T)>>(iter: I) -> Self {
+        Self { values: iter.into_iter().collect() }
+    }
+}
+
+impl<S, X, T> Extend<(Id<S, X>, T)> for IdMap<S, X, T> {
+    fn extend<I: IntoIterator<Item = (Id<S, X>, T)>>(&mut self, iter: I) {
+        self.values.extend(iter)
+    }
+}
+
+impl<S, X, T> IntoIterator for IdMap<S, X, T> {
+    type Item = (Id<S, X>, T);
+    type IntoIter = std::collections::btree_map::IntoIter<Id<S, X>, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.values.into_iter() }
+}
+
+impl<S, X, T> Default for IdMap<S, X, T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<S, X, T: Clone> Clone for IdMap<S, X, T> {
+    fn clone(&self) -> Self { Self { values: self.values.clone() } }
+}
+
+impl<S, X, T: fmt::Debug> fmt::Debug for IdMap<S, X, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<S, X, T: PartialEq> PartialEq for IdMap<S, X, T> {
+    fn eq(&self, other: &Self) -> bool { self.values == other.values }
+}
+
+impl<S, X, T: Eq> Eq for IdMap<S, X, T> {}
+
+/// Serializes the values of an [`IdMap`] as a sequence of entries.
+#[cfg(feature = "serde")]
+mod id_map {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Id;
+
+    pub fn serialize<S, X, T: Serialize, Ser: Serializer>(
+        map: &BTreeMap<Id<S, X>, T>,
+        serializer: Ser,
+    ) -> Result<Ser::Ok, Ser::Error> {
+        serializer.collect_seq(map)
+    }
+
+    pub fn deserialize<'de, S, X, T: Deserialize<'de>, De: Deserializer<'de>>(
+        deserializer: De,
+    ) -> Result<BTreeMap<Id<S, X>, T>, De::Error> {
+        Ok(Vec::<(Id<S, X>, T)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
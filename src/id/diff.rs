@@ -0,0 +1,255 @@
+use std::any::TypeId;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use getset::Getters;
+
+use super::{hash_key, recorded_names, Id, IdIndexing, IdKey, KeyedIdentifiable};
+use crate::Schema;
+
+/// The changes of the IDs between two conversions,
+/// e.g. to patch the references held by a running system after hot reloading.
+///
+/// IDs are compared by key within each scope.
+/// A removed ID and an added ID at the same index are considered a rename,
+/// e.g. when the name of an object is edited in place.
+/// For types with [`IdIndexing::Hashed`],
+/// the declaration order is compared instead of the indices.
+///
+/// Scoped IDs can only be compared if they are declared with `track = true`.
+/// They are compared with respect to the scope they are declared in,
+/// so the scope type should be compared before the types scoped in it
+/// in order to remap the ID paths of the scopes.
+/// If the scope type is not compared, its indices are assumed to be unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct IdDiff {
+    types: HashMap<TypeId, TypeDiff>,
+}
+
+/// The changes of the IDs of a type.
+#[derive(Debug, Clone, Default)]
+struct TypeDiff {
+    scopes:        Vec<ScopeDiff>,
+    /// The indices in `scopes` keyed by the old ID path of the scope.
+    by_old_parent: BTreeMap<Vec<usize>, usize>,
+}
+
+/// The changes of the IDs of a type in a scope.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+pub struct ScopeDiff {
+    /// The ID path of the scope in the old conversion,
+    /// or `None` if the scope was added.
+    #[getset(get = "pub")]
+    old_parent: Option<Vec<usize>>,
+    /// The ID path of the scope in the new conversion,
+    /// or `None` if the scope was removed.
+    #[getset(get = "pub")]
+    new_parent: Option<Vec<usize>>,
+    /// The display forms of the keys of the added IDs, ordered by new index.
+    #[getset(get = "pub")]
+    added:      Vec<String>,
+    /// The display forms of the keys of the removed IDs, ordered by old index.
+    #[getset(get = "pub")]
+    removed:    Vec<String>,
+    /// The display forms of the old and new keys of the renamed IDs, ordered by index.
+    #[getset(get = "pub")]
+    renamed:    Vec<(String, String)>,
+    /// Maps the old index of each kept or renamed ID to its new index.
+    #[getset(get = "pub")]
+    remap:      BTreeMap<usize, usize>,
+}
+
+impl ScopeDiff {
+    /// Returns whether the scope and the IDs in it are unchanged,
+    /// i.e. references to them do not need to be patched.
+    pub fn is_unchanged(&self) -> bool {
+        self.old_parent == self.new_parent
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.remap.iter().all(|(old, new)| old == new)
+    }
+}
+
+impl IdDiff {
+    /// Compares the `X` IDs in the contexts of two conversions.
+    pub fn compare<S: Schema, X: KeyedIdentifiable<S>>(
+        &mut self,
+        old: &<S as Schema>::Context,
+        new: &<S as Schema>::Context,
+    ) {
+        let old_scopes = recorded_names::<S, X>(old);
+        let new_scopes = recorded_names::<S, X>(new);
+        let scope_type = TypeId::of::<X::Scope>();
+        let recursive = scope_type == TypeId::of::<X>();
+
+        // the key order ensures that the scope of a recursive type is compared before its children
+        let mut type_diff = TypeDiff::default();
+        let mut matched = HashSet::new();
+        for (old_parent, old_names) in &old_scopes {
+            let scope_diff = if recursive { Some(&type_diff) } else { self.types.get(&scope_type) };
+            let new_parent = translate(scope_diff, old_parent);
+            let new_names = new_parent.as_ref().and_then(|parent| new_scopes.get(parent));
+            if let Some(parent) = &new_parent {
+                matched.insert(parent.clone());
+            }
+
+            let new_names = new_names.map_or(&[][..], Vec::as_slice);
+            let mut diff = compare_names(old_names, new_names);
+            if X::indexing() == IdIndexing::Hashed {
+                diff.remap = hash_remap(&diff.remap, old_names, new_names);
+            }
+            diff.old_parent = Some(old_parent.clone());
+            diff.new_parent = new_parent;
+            type_diff.by_old_parent.insert(old_parent.clone(), type_diff.scopes.len());
+            type_diff.scopes.push(diff);
+        }
+
+        for (new_parent, new_names) in &new_scopes {
+            if !matched.contains(new_parent) {
+                let mut diff = compare_names(&[], new_names);
+                diff.new_parent = Some(new_parent.clone());
+                type_diff.scopes.push(diff);
+            }
+        }
+
+        self.types.insert(TypeId::of::<X>(), type_diff);
+    }
+
+    /// Returns the changes of the `X` IDs in each scope.
+    ///
+    /// Returns an empty slice if `X` was not compared.
+    pub fn scopes<S: Schema, X: KeyedIdentifiable<S>>(&self) -> &[ScopeDiff] {
+        match self.types.get(&TypeId::of::<X>()) {
+            Some(type_diff) => &type_diff.scopes,
+            None => &[],
+        }
+    }
+
+    /// Returns the changes of the `X` IDs in the scope
+    /// with the ID path `old_parent` in the old conversion.
+    pub fn scope<S: Schema, X: KeyedIdentifiable<S>>(
+        &self,
+        old_parent: &[usize],
+    ) -> Option<&ScopeDiff> {
+        let type_diff = self.types.get(&TypeId::of::<X>())?;
+        Some(&type_diff.scopes[*type_diff.by_old_parent.get(old_parent)?])
+    }
+
+    /// Remaps an ID from the old conversion to the new conversion,
+    /// where `old_parent` is the ID path of its scope in the old conversion.
+    ///
+    /// Returns `None` if the ID was removed or `X` was not compared.
+    pub fn remap<S: Schema, X: KeyedIdentifiable<S>>(
+        &self,
+        old_parent: &[usize],
+        id: Id<S, X>,
+    ) -> Option<Id<S, X>> {
+        let scope = self.scope::<S, X>(old_parent)?;
+        scope.remap.get(&id.index()).map(|&index| Id::new(index))
+    }
+}
+
+/// Translates the ID path of a scope from the old conversion to the new conversion,
+/// using the changes of the scope type.
+///
+/// Returns `None` if the scope was removed.
+fn translate(scope_diff: Option<&TypeDiff>, path: &[usize]) -> Option<Vec<usize>> {
+    let (&last, prefix) = match path.split_last() {
+        Some(split) => split,
+        None => return Some(Vec::new()),
+    };
+    let scope_diff = match scope_diff {
+        Some(scope_diff) => scope_diff,
+        // the scope type was not compared
+        None => return Some(path.to_vec()),
+    };
+
+    let parent = &scope_diff.scopes[*scope_diff.by_old_parent.get(prefix)?];
+    let mut new_path = parent.new_parent.clone()?;
+    new_path.push(*parent.remap.get(&last)?);
+    Some(new_path)
+}
+
+/// Compares the keys in a scope,
+/// where reserved indices that were not declared are `None`.
+fn compare_names<K: IdKey>(old: &[Option<&K>], new: &[Option<&K>]) -> ScopeDiff {
+    let new_indices: HashMap<&K, usize> =
+        new.iter().enumerate().filter_map(|(index, name)| Some(((*name)?, index))).collect();
+    let old_names: HashSet<&K> = old.iter().flatten().copied().collect();
+
+    let mut diff = ScopeDiff::default();
+
+    let mut removed = BTreeSet::new();
+    for (index, name) in old.iter().enumerate() {
+        if let Some(name) = name {
+            match new_indices.get(name) {
+                Some(&new_index) => {
+                    diff.remap.insert(index, new_index);
+                }
+                None => {
+                    removed.insert(index);
+                }
+            }
+        }
+    }
+
+    for (index, name) in new.iter().enumerate() {
+        let name = match name {
+            Some(name) if !old_names.contains(name) => name,
+            _ => continue,
+        };
+        match old.get(index) {
+            Some(Some(old_name)) if removed.remove(&index) => {
+                diff.renamed.push((old_name.to_string(), name.to_string()));
+                diff.remap.insert(index, index);
+            }
+            _ => diff.added.push(name.to_string()),
+        }
+    }
+
+    diff.removed =
+        removed.into_iter().filter_map(|index| old[index]).map(ToString::to_string).collect();
+    diff
+}
+
+/// Converts a remap between positions in the declaration order
+/// to a remap between the hashed indices of the keys.
+fn hash_remap<K: IdKey>(
+    remap: &BTreeMap<usize, usize>,
+    old: &[Option<&K>],
+    new: &[Option<&K>],
+) -> BTreeMap<usize, usize> {
+    let hash = |name: Option<&K>| hash_key(name.expect("remapped IDs are declared")) as usize;
+    remap
+        .iter()
+        .map(|(&old_position, &new_position)| (hash(old[old_position]), hash(new[new_position])))
+        .collect()
+}
+
+/// A list of [`Identifiable`](super::Identifiable) types to compare with [`IdDiff`].
+///
+/// This is implemented for tuples of up to 8 types,
+/// which are compared in order.
+pub trait IdTypes<S: Schema> {
+    /// Compares the IDs of each type in the contexts of two conversions.
+    fn compare(diff: &mut IdDiff, old: &<S as Schema>::Context, new: &<S as Schema>::Context);
+}
+
+macro_rules! impl_id_types {
+    ($($ty:ident),+) => {
+        impl<S: Schema, $($ty: KeyedIdentifiable<S>),+> IdTypes<S> for ($($ty,)+) {
+            fn compare(diff: &mut IdDiff, old: &<S as Schema>::Context, new: &<S as Schema>::Context) {
+                $(diff.compare::<S, $ty>(old, new);)+
+            }
+        }
+    };
+}
+
+impl_id_types!(A);
+impl_id_types!(A, B);
+impl_id_types!(A, B, C);
+impl_id_types!(A, B, C, D);
+impl_id_types!(A, B, C, D, E);
+impl_id_types!(A, B, C, D, E, F);
+impl_id_types!(A, B, C, D, E, F, G);
+impl_id_types!(A, B, C, D, E, F, G, H);
@@ -0,0 +1,152 @@
+use std::any::TypeId;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use super::{GlobalIdStore, Id, IdCounter, IdIndexing, IdNames, KeyedIdentifiable};
+use crate::{Context, Schema};
+
+/// A snapshot of the keys of the `X` IDs after conversion,
+/// e.g. to display IDs in logs or to parse IDs from user input.
+///
+/// The registry contains the global IDs (where `X::Scope` is `()`)
+/// and the scoped IDs declared with `track = true`,
+/// keyed by the ID path of their scope.
+/// Indices reserved by a seeded [`IdTable`](super::IdTable) but not declared have no key.
+/// The keys are kept in declaration order,
+/// which is the index order unless `X` uses [`IdIndexing::Hashed`].
+///
+/// # Example
+/// ```
+/// use xylem::id::IdRegistry;
+/// use xylem::{DefaultContext, Id, Identifiable, NoArgs, Xylem};
+///
+/// xylem::declare_schema!(Schema: xylem::SchemaExt);
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Schema, expose = ItemFrom)]
+/// struct Item {
+///     #[xylem(args(new = true))]
+///     id: Id<Schema, Item>,
+/// }
+///
+/// impl Identifiable<Schema> for Item {
+///     type Scope = ();
+///     fn id(&self) -> Id<Schema, Item> { self.id }
+/// }
+///
+/// let mut context = DefaultContext::default();
+/// let items = vec![ItemFrom { id: String::from("sword") }, ItemFrom { id: String::from("bow") }];
+/// let items = Vec::<Item>::convert(items, &mut context, &NoArgs).unwrap();
+///
+/// let registry = IdRegistry::<Schema, Item>::from_context(&context);
+/// assert_eq!(registry.name(items[1].id).map(String::as_str), Some("bow"));
+/// assert_eq!(registry.lookup("sword"), Some(items[0].id));
+/// ```
+pub struct IdRegistry<S: Schema, X: KeyedIdentifiable<S>> {
+    scopes: BTreeMap<Vec<usize>, IdNames<X::Key>>,
+    /// The position of each hashed index in `scopes`.
+    hashes: BTreeMap<Vec<usize>, HashMap<usize, usize>>,
+    _ph:    PhantomData<fn() -> (S, X)>,
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> IdRegistry<S, X> {
+    /// Collects the keys of the `X` IDs in the context.
+    pub fn from_context(context: &<S as Schema>::Context) -> Self {
+        let mut scopes = BTreeMap::new();
+
+        if let Some(store) = context.get::<GlobalIdStore<S, X>>(TypeId::of::<()>()) {
+            scopes.extend(store.ids.iter().map(|(parent, names)| (parent.clone(), names.clone())));
+        }
+        if let Some(counter) = context.get::<IdCounter<S, X>>(TypeId::of::<()>()) {
+            let names = (0..counter.names.len())
+                .map(|position| counter.name_at(position).cloned())
+                .collect();
+            scopes.insert(Vec::new(), names);
+        }
+
+        let mut hashes = BTreeMap::new();
+        if X::indexing() == IdIndexing::Hashed {
+            for (parent, names) in &scopes {
+                let positions = names.iter().enumerate().filter_map(|(position, name)| {
+                    Some((X::indexing().index(name.as_ref()?, position), position))
+                });
+                hashes.insert(parent.clone(), positions.collect());
+            }
+        }
+
+        Self { scopes, hashes, _ph: PhantomData }
+    }
+
+    /// Returns the key of a global ID.
+    pub fn name(&self, id: Id<S, X>) -> Option<&X::Key> { self.name_in(&[], id) }
+
+    /// Returns the global ID with the key.
+    pub fn lookup<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<Id<S, X>>
+    where
+        X::Key: Borrow<Q>,
+    {
+        self.lookup_in(&[], key)
+    }
+
+    /// Iterates over the global IDs and their keys in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id<S, X>, &X::Key)> + '_ { self.iter_in(&[]) }
+
+    /// Returns the key of an ID in the scope with the ID path `parent`.
+    pub fn name_in(&self, parent: &[usize], id: Id<S, X>) -> Option<&X::Key> {
+        let position = match X::indexing() {
+            IdIndexing::Sequential => id.index(),
+            IdIndexing::Hashed => *self.hashes.get(parent)?.get(&id.index())?,
+        };
+        self.scopes.get(parent)?.name(position)
+    }
+
+    /// Returns the ID with the key in the scope with the ID path `parent`.
+    pub fn lookup_in<Q: Eq + Hash + ?Sized>(&self, parent: &[usize], key: &Q) -> Option<Id<S, X>>
+    where
+        X::Key: Borrow<Q>,
+    {
+        let names = self.scopes.get(parent)?;
+        let position = names.position(key)?;
+        Id::try_new(X::indexing().index(names.name(position)?, position))
+    }
+
+    /// Iterates over the IDs and their keys
+    /// in the scope with the ID path `parent` in declaration order.
+    pub fn iter_in(&self, parent: &[usize]) -> impl Iterator<Item = (Id<S, X>, &X::Key)> + '_ {
+        self.scopes.get(parent).into_iter().flat_map(|names| names.iter().enumerate()).filter_map(
+            |(position, name)| {
+                let name = name.as_ref()?;
+                Some((Id::try_new(X::indexing().index(name, position))?, name))
+            },
+        )
+    }
+
+    /// Returns the number of IDs in the scope with the ID path `parent`,
+    /// including the indices reserved by a seeded [`IdTable`](super::IdTable).
+    pub fn len_in(&self, parent: &[usize]) -> usize {
+        self.scopes.get(parent).map_or(0, |names| names.len())
+    }
+
+    /// Iterates over the ID paths of the scopes containing `X` IDs.
+    pub fn scopes(&self) -> impl Iterator<Item = &[usize]> + '_ {
+        self.scopes.keys().map(Vec::as_slice)
+    }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> fmt::Debug for IdRegistry<S, X>
+where
+    X::Key: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IdRegistry").field("scopes", &self.scopes).finish()
+    }
+}
+
+impl<S: Schema, X: KeyedIdentifiable<S>> Clone for IdRegistry<S, X> {
+    fn clone(&self) -> Self {
+        Self { scopes: self.scopes.clone(), hashes: self.hashes.clone(), _ph: PhantomData }
+    }
+}
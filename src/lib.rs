@@ -66,6 +66,10 @@
 //! // we defined a schema type called `Schema`.
 //! ```
 //!
+//! A schema can also inherit the conversions of another schema,
+//! e.g. a base schema shared by multiple products with per-product overrides.
+//! See [`InheritSchema`] for details.
+//!
 //! It is recommended to use `Schema` as the schema name
 //! and declare it at the crate level,
 //! because the [`Xylem`][xylem_codegen::Xylem] macro
@@ -83,6 +87,10 @@
 //! because xylem type conversion is stateful,
 //! i.e. previous conversions may affect subsequent ones.
 //!
+//! [`Session`] is a convenient entry point to convert a root value
+//! in a fresh context,
+//! returning the value with a report of the IDs and warnings it produced.
+//!
 //!
 //! ## The `id` feature
 //! With the `id` feature enabled,
@@ -104,6 +112,10 @@
 //! must occur also as transitive children of the scope,
 //! because the scope is dropped when it completes parsing.
 //!
+//! IDs are written as strings in the config.
+//! If they are written as other types, e.g. integers,
+//! implement [`id::KeyedIdentifiable`] with the key type instead of [`Identifiable`].
+//!
 //! Declaring IDs are marked with the argument `new = true`.
 //! If the ID is to be cross-referenced after the scope drops,
 //! also mark `track = true`.
@@ -114,18 +126,36 @@
 //! See [tests/id.rs](https://docs.rs/crate/xylem/*/source/tests/id.rs) and
 //! [tests/cross\_id.rs](https://docs.rs/crate/xylem/*/source/tests/cross_id.rs) for example usage.
 //!
-//! Note that it is not a design goal for xylem to support lookahead IDs.
 //! Due to the stateful nature of xylem,
 //! IDs are only indexed when the declaration has been scanned.
-//! There is currently no plan to implement multiple passes
-//! to pre-index IDs.
+//! To reference an ID declared later,
+//! use [`LazyId`] instead of [`Id`],
+//! which is resolved when the scope of the namespace ends.
+//! Global [`LazyId`]s are resolved when [`finalize`] is called.
 
 use std::any::TypeId;
 use std::fmt;
+use std::ops::{Deref, DerefMut, Range};
 
 // An internal re-export used for reusing arguments.
 #[doc(hidden)]
 pub use lazy_static::lazy_static;
+
+/// An internal trait used by the [`Xylem`][xylem_codegen::Xylem] macro
+/// to check that the variants of an untagged enum have the same derived field type.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "the variants of an untagged enum must have the same derived field type",
+    label = "the derived field type of this variant is `{Self}`, but the first variant has `{T}`"
+)]
+pub trait UntaggedFrom<T> {}
+
+impl<T> UntaggedFrom<T> for T {}
+
+#[doc(hidden)]
+#[inline]
+pub fn assert_untagged_from<T: UntaggedFrom<U>, U>() {}
+
 /// Derives a [`Xylem`] implementation for a struct or enum
 /// and the corresponding [`Xylem::From`] type.
 ///
@@ -162,6 +192,37 @@ pub use lazy_static::lazy_static;
 ///
 /// Requires the input type to implement the [`Processable`] trait.
 ///
+/// ## `#[xylem(inverse)]`
+/// Also derive a [`XylemInverse`] implementation,
+/// which converts the input type back to the derived type.
+///
+/// Every field that appears in the derived type must support the inverse conversion,
+/// i.e. standard fields must implement [`XylemInverse`],
+/// and `transform`/`transform_with_context` fields must specify `inverse`.
+/// `default` fields are dropped.
+///
+/// ## `#[xylem(spanned)]`
+/// Wrap the derived type of each standard field in [`Spanned`],
+/// i.e. `foo: Bar` generates `foo: Spanned<<Bar as Xylem<S>>::From>`,
+/// so that the source span of each field is available
+/// through [`Context::span`] during conversion,
+/// e.g. to locate duplicate and unknown [`Id`]s.
+/// The spans are discarded after conversion.
+/// Fields with `preserve`, `transform`, `transform_with_context` or `default` are not affected.
+///
+/// ## `#[xylem(untagged)]`
+/// Only applicable on enums.
+/// Each variant must have exactly one field that appears in the derived type,
+/// and the derived field types of all variants must be the same type implementing [`Clone`].
+/// A variant with a different derived field type is reported as a compile error on its field.
+///
+/// The derived type is a tuple struct wrapping the derived field type.
+/// During conversion, each variant is attempted in order
+/// in a [`Context::transaction`],
+/// so the side effects of failed attempts (e.g. declared IDs) are rolled back.
+/// The first successful variant is used.
+/// If all variants fail, the errors of all attempts are merged with [`AbstractError::merge`].
+///
 /// # Field Attributes
 /// The following attributes can be applied on the fields in the input.
 /// As above, "input field" refers to the field written by the user manually,
@@ -197,6 +258,13 @@ pub use lazy_static::lazy_static;
 /// The expression should have type `Result<Field, S::Error>`,
 /// where `Field` is the field type.
 ///
+/// ## `#[xylem(inverse = path)]`
+/// The inverse of `transform` or `transform_with_context`,
+/// used when the container derives [`XylemInverse`].
+/// `path` is the path to a function with the signature
+/// `fn(Bar) -> Result<Type, S::Error>`,
+/// or `fn(Bar, &mut S::Context) -> Result<Type, S::Error>` for `transform_with_context`.
+///
 /// Comparing `default`, `preserve`, `transform` and `transform_with_context`:
 /// - If a corresponding field is required in the derived type,
 ///     - If they have different types,
@@ -226,11 +294,33 @@ pub use xylem_codegen::Xylem;
 #[cfg(feature = "id")]
 pub mod id;
 #[cfg(feature = "id")]
-pub use id::{Id, IdArgs, IdString, Identifiable};
+pub use id::{Id, IdAliases, IdArgs, IdMap, IdString, IdVec, Identifiable, LazyId};
 #[cfg(feature = "ext")]
 mod ext;
 #[cfg(feature = "ext")]
 pub use ext::*;
+mod diagnostics;
+pub use diagnostics::Warning;
+mod error;
+pub use error::ErrorCollector;
+mod finalize;
+#[cfg(feature = "anyhow")]
+pub use error::ErrorList;
+pub use finalize::{defer, defer_at, finalize, Finalizer};
+mod observer;
+#[cfg(feature = "tracing")]
+pub use observer::TracingObserver;
+pub use observer::{ScopeEnd, ScopeObserver, ScopeOutcome, ScopeStart};
+mod session;
+pub use session::{Report, Session};
+mod span;
+#[cfg(feature = "serde")]
+pub use span::deserialize_without_spans;
+pub use span::{LineColumn, Spanned};
+mod path;
+#[cfg(feature = "anyhow")]
+pub use path::PathError;
+pub use path::{FieldPath, PathSegment};
 
 /// Implementors of this trait have a special conversion rule under the schema `Schema`.
 pub trait Xylem<S: Schema + ?Sized>: Sized + 'static {
@@ -255,6 +345,8 @@ pub trait Xylem<S: Schema + ?Sized>: Sized + 'static {
 
     /// Converts the `From` type to the `Self` type,
     /// registering the scope with the context.
+    /// Finalizers registered in the scope with [`defer`] are called
+    /// after a successful conversion.
     /// Do not override this method.
     #[inline]
     fn convert(
@@ -262,10 +354,19 @@ pub trait Xylem<S: Schema + ?Sized>: Sized + 'static {
         context: &mut <S as Schema>::Context,
         args: &Self::Args,
     ) -> Result<Self, <S as Schema>::Error> {
-        let scope = context.start_scope::<Self>();
-        let ret = Self::convert_impl(from, context, args)?;
-        context.end_scope(scope);
-        Ok(ret)
+        let root_mark = finalize::root_finalizer_mark::<S>(context);
+        let mut context = ScopeGuard::new::<Self>(context);
+        let ret = Self::convert_impl(from, &mut context, args)
+            .and_then(|value| {
+                finalize::run_finalizers::<S>(&mut context, TypeId::of::<Self>())?;
+                Ok(value)
+            })
+            .map_err(|err| err.with_path(context.path()));
+        if let (Err(_), Some(mark)) = (&ret, root_mark) {
+            finalize::discard_root_finalizers::<S>(&mut context, mark);
+        }
+        context.set_outcome(ret.is_ok());
+        ret
     }
 
     /// The implementation of the conversion.
@@ -293,6 +394,95 @@ where
     }
 }
 
+/// Implementors of this trait can be converted back to their [`Xylem::From`] type.
+///
+/// The inverse conversion visits the value in the same order as [`Xylem::convert`],
+/// so stateful conversions such as [`Id`] can map the value back
+/// using the state recorded in the context during the original conversion.
+///
+/// [`HashMap`](std::collections::HashMap) is an exception:
+/// its entries are visited in iteration order,
+/// which can differ between the converted map and the original one.
+/// Declarations of new IDs in `HashMap` entries may therefore fail to convert back;
+/// use a `BTreeMap` or a `Vec` for such entries instead.
+pub trait XylemInverse<S: Schema + ?Sized>: Xylem<S> {
+    /// Converts the `Self` type back to the `From` type,
+    /// registering the scope with the context.
+    /// Do not override this method.
+    #[inline]
+    fn unconvert(
+        self,
+        context: &mut <S as Schema>::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, <S as Schema>::Error> {
+        let mut context = ScopeGuard::new::<Self>(context);
+        let ret =
+            self.unconvert_impl(&mut context, args).map_err(|err| err.with_path(context.path()));
+        context.set_outcome(ret.is_ok());
+        ret
+    }
+
+    /// The implementation of the inverse conversion.
+    fn unconvert_impl(
+        self,
+        context: &mut <S as Schema>::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, <S as Schema>::Error>;
+}
+
+impl<S> XylemInverse<S> for ()
+where
+    S: Schema,
+{
+    fn unconvert_impl(
+        self,
+        _: &mut <S as Schema>::Context,
+        _: &Self::Args,
+    ) -> Result<Self::From, <S as Schema>::Error> {
+        Ok(())
+    }
+}
+
+/// Starts a scope on creation and ends it on drop,
+/// so that the scope stack stays balanced
+/// even if the conversion returns an error or panics.
+struct ScopeGuard<'t, C: Context> {
+    context: &'t mut C,
+    scope:   Option<C::Scope>,
+    /// The outcome of the conversion,
+    /// or [`ScopeOutcome::Panic`] if the guard is dropped before the conversion returns.
+    outcome: ScopeOutcome,
+}
+
+impl<'t, C: Context> ScopeGuard<'t, C> {
+    fn new<T: 'static>(context: &'t mut C) -> Self {
+        let scope = context.start_scope::<T>();
+        Self { context, scope: Some(scope), outcome: ScopeOutcome::Panic }
+    }
+
+    fn set_outcome(&mut self, ok: bool) {
+        self.outcome = if ok { ScopeOutcome::Success } else { ScopeOutcome::Failure };
+    }
+}
+
+impl<'t, C: Context> Deref for ScopeGuard<'t, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C { self.context }
+}
+
+impl<'t, C: Context> DerefMut for ScopeGuard<'t, C> {
+    fn deref_mut(&mut self) -> &mut C { self.context }
+}
+
+impl<'t, C: Context> Drop for ScopeGuard<'t, C> {
+    fn drop(&mut self) {
+        if let Some(scope) = self.scope.take() {
+            self.context.end_scope_with_outcome(scope, self.outcome);
+        }
+    }
+}
+
 /// Preprocessor and postprocessor extensions for [`Xylem`].
 pub trait Processable<S: Schema + ?Sized>: Xylem<S> {
     /// This method is called at the beginning of [`Xylem::convert_impl`] if `#[xylem(process)]` is
@@ -326,15 +516,105 @@ pub trait Schema: 'static {
     type Error: AbstractError;
 }
 
+/// A schema that inherits the conversions of another schema.
+///
+/// Since Rust does not support specialization,
+/// conversions are not inherited automatically.
+/// Use the [`inherit`] macro to delegate the conversion of specific types
+/// to the parent schema,
+/// and implement [`Xylem`] for the child schema directly to override them.
+/// The `*SchemaExt` traits are not inherited either,
+/// but they can be listed in [`declare_schema`] after the parent schema.
+///
+/// The parent schema must have the same context and error types,
+/// so inherited conversions share the context state with the child schema,
+/// including ID namespaces and [finalizers](crate::defer).
+/// To keep ID types consistent between both schemas,
+/// inherit `Id<Parent, X>` and use it in the types of the child schema
+/// instead of declaring `Id<Child, X>`.
+///
+/// # Example
+/// ```
+/// use xylem::{declare_schema, inherit, Xylem};
+///
+/// declare_schema!(Base: xylem::SchemaExt);
+/// declare_schema!(Child: extends Base, xylem::SchemaExt);
+///
+/// struct Celsius(f64);
+///
+/// impl Xylem<Base> for Celsius {
+///     type From = f64;
+///     type Args = xylem::NoArgs;
+///
+///     fn convert_impl(
+///         from: f64,
+///         _: &mut xylem::DefaultContext,
+///         _: &xylem::NoArgs,
+///     ) -> anyhow::Result<Self> {
+///         Ok(Self(from))
+///     }
+/// }
+///
+/// inherit!(Child: Celsius);
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Child)]
+/// struct Weather {
+///     temperature: Celsius,
+/// }
+/// ```
+pub trait InheritSchema: Schema {
+    /// The schema to inherit conversions from.
+    type Parent: Schema<Context = Self::Context, Error = Self::Error>;
+}
+
 /// The error type for a schema.
 pub trait AbstractError: Sized {
     /// Creates a new error type.
     fn new<T: fmt::Display>(msg: T) -> Self;
+
+    /// Annotates the error with the path of the value that failed to convert.
+    ///
+    /// This method is called from [`Xylem::convert`] for each scope that the error passes through,
+    /// starting from the innermost one.
+    /// Implementations should only keep the first (i.e. the most specific) path.
+    ///
+    /// The default implementation discards the path.
+    fn with_path(self, _path: FieldPath) -> Self { self }
+
+    /// Annotates the error with the source spans that it refers to,
+    /// e.g. the declarations of a duplicate ID.
+    ///
+    /// The spans are byte ranges in the source file,
+    /// which can be located with [`LineColumn::of`].
+    ///
+    /// The default implementation discards the spans.
+    fn with_spans(self, _spans: Vec<Range<usize>>) -> Self { self }
+
+    /// Merges two errors into one,
+    /// used when [`Context::collect_errors`] is enabled.
+    ///
+    /// The default implementation discards `other`.
+    fn merge(self, _other: Self) -> Self { self }
 }
 
 #[cfg(feature = "anyhow")]
 impl AbstractError for anyhow::Error {
     fn new<T: fmt::Display>(msg: T) -> Self { anyhow::anyhow!("{}", msg) }
+
+    /// Wraps the error in a [`PathError`] if it does not have a path yet.
+    ///
+    /// For an [`ErrorList`], each error is wrapped separately.
+    fn with_path(self, path: FieldPath) -> Self {
+        ErrorList::map(self, |err| PathError::wrap(err, path.clone()))
+    }
+
+    /// Wraps the error in a [`PathError`] with the spans,
+    /// which is given a path when the error leaves the scope.
+    fn with_spans(self, spans: Vec<Range<usize>>) -> Self { PathError::with_spans(self, spans) }
+
+    /// Merges the errors into an [`ErrorList`].
+    fn merge(self, other: Self) -> Self { ErrorList::merge(self, other) }
 }
 
 /// The context of a conversion.
@@ -347,23 +627,68 @@ impl AbstractError for anyhow::Error {
 /// Each layer of the stack has its own typemap,
 /// which provides access to an arbitrary object bound accessible during the scope.
 ///
-/// It is strongly discouraged to have recursive types
-/// resulting in multiple layers of the scope to have the same type ID,
-/// which may need to strange behavior when accessing the typemap,
-/// becaues only the newest layer of the type is accessible.
+/// [`Xylem::convert`] and [`XylemInverse::unconvert`] always end the scopes they start,
+/// even if the conversion returns an error or panics,
+/// so a context can be reused for further conversions after a failure.
+/// Only the storage in the scopes that are still active is retained,
+/// e.g. global IDs declared before the failure remain declared.
+///
+/// Recursive types result in multiple layers with the same type ID.
+/// Methods that take a scope type ID access the newest layer of the type;
+/// use [`Context::find_layer`] with a [`LayerSelector`]
+/// to access the other layers.
+///
+/// Storage accessed through the `*_journaled` methods
+/// can be rolled back to a [`Checkpoint`].
+/// Storage accessed through [`Context::get_mut`] is not journaled,
+/// so it does not need to implement [`Clone`].
 pub trait Context: Default {
     /// Identifies a layer of scope.
     type Scope;
 
     /// Gets the nth topmost scope type ID.
+    ///
+    /// `n = 0` is the current scope.
+    /// Returns `None` if the stack has `n` or fewer layers.
     fn nth_last_scope(&self, n: usize) -> Option<TypeId>;
 
+    /// Finds the index of a layer of type `scope` in the stack,
+    /// where the root scope has index 0.
+    ///
+    /// If there are multiple layers of the same type (e.g. for recursive types),
+    /// `selector` specifies which one to use.
+    ///
+    /// The default implementation walks the stack with [`Context::nth_last_scope`].
+    fn find_layer(&self, scope: TypeId, selector: LayerSelector) -> Option<usize> {
+        let depth = stack_depth(self);
+        let matches = |index: &usize| self.nth_last_scope(depth - 1 - index) == Some(scope);
+        match selector {
+            LayerSelector::Nearest => (0..depth).rev().find(matches),
+            LayerSelector::Outermost => (0..depth).find(matches),
+            LayerSelector::Nth(n) => (0..depth).rev().filter(matches).nth(n),
+            LayerSelector::Below(index) => (0..index.min(depth)).rev().find(matches),
+        }
+    }
+
     /// Gets a shared reference to the storage of type `T`
     /// in the newest layer of the scope.
     fn get<T>(&self, scope: TypeId) -> Option<&T>
     where
         T: 'static;
 
+    /// Gets a shared reference to the storage of type `T`
+    /// in the layer at the index returned by [`Context::find_layer`].
+    ///
+    /// The default implementation only supports the newest layer of each scope type
+    /// and returns `None` for the other layers.
+    /// Contexts that support recursive scope types should override this method.
+    fn get_at<T>(&self, layer: usize) -> Option<&T>
+    where
+        T: 'static,
+    {
+        self.get(newest_scope_at(self, layer)?)
+    }
+
     /// Gets a shared reference to the storage of type `T`
     /// in each layer, from top to bottom, if exists.
     fn get_each<T>(&self) -> Box<dyn Iterator<Item = &T> + '_>
@@ -371,23 +696,310 @@ pub trait Context: Default {
         T: 'static;
 
     /// Gets a mutable reference to the storage of type `T`
-    /// in the newest layer of the scope.
+    /// in the newest layer of the scope,
+    /// initializing it with `default` if it does not exist.
+    ///
+    /// Changes made through this method are not undone by [`Context::rollback`].
+    /// Use [`Context::get_mut_journaled`] for storage that should be rolled back.
+    ///
+    /// # Panics
+    /// Panics if the scope is not in the stack.
+    /// Use [`Context::try_get_mut`] if the scope may be absent.
     fn get_mut<T, F>(&mut self, scope: TypeId, default: F) -> &mut T
     where
         F: FnOnce() -> T,
         T: 'static;
 
+    /// Gets a mutable reference to the storage of type `T`
+    /// in the newest layer of the scope,
+    /// initializing it with `default` if it does not exist.
+    ///
+    /// Returns `None` if the scope is not in the stack.
+    fn try_get_mut<T, F>(&mut self, scope: TypeId, default: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+        T: 'static,
+    {
+        self.find_layer(scope, LayerSelector::Nearest)?;
+        Some(self.get_mut(scope, default))
+    }
+
+    /// Gets a mutable reference to the storage of type `T`
+    /// in the layer at the index returned by [`Context::find_layer`],
+    /// initializing it with `default` if it does not exist.
+    ///
+    /// Returns `None` if there is no layer at the index.
+    ///
+    /// The default implementation only supports the newest layer of each scope type
+    /// and returns `None` for the other layers.
+    /// Contexts that support recursive scope types should override this method.
+    fn try_get_mut_at<T, F>(&mut self, layer: usize, default: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+        T: 'static,
+    {
+        let scope = newest_scope_at(self, layer)?;
+        Some(self.get_mut(scope, default))
+    }
+
+    /// Gets a mutable reference to the storage of type `T`
+    /// in the layer at the index returned by [`Context::find_layer`],
+    /// initializing it with `default` if it does not exist.
+    ///
+    /// Unlike [`Context::try_get_mut_at`],
+    /// the changes made through the reference are undone by [`Context::rollback`].
+    /// `T` must implement [`Clone`] so that the storage can be restored.
+    ///
+    /// The default implementation does not journal the storage,
+    /// consistent with the default implementation of [`Context::checkpoint`].
+    fn try_get_mut_journaled_at<T, F>(&mut self, layer: usize, default: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+        T: Clone + 'static,
+    {
+        self.try_get_mut_at(layer, default)
+    }
+
+    /// Journals how to undo a change to the storage of type `T`
+    /// in the layer at the index returned by [`Context::find_layer`],
+    /// so that [`Context::rollback`] reverses the change.
+    ///
+    /// This is an alternative to [`Context::try_get_mut_journaled_at`]
+    /// for storage that is too large to be cloned after each checkpoint,
+    /// e.g. lists that only grow and are restored by truncating them.
+    /// It must be called before each change to the storage.
+    /// If a checkpoint is active,
+    /// `undo` is called with the storage to create an undo entry,
+    /// which is called with the storage on rollback.
+    /// If the storage does not exist yet, it is removed on rollback instead.
+    ///
+    /// The default implementation does nothing,
+    /// consistent with the default implementation of [`Context::checkpoint`].
+    fn journal_undo_at<T, F, U>(&mut self, _layer: usize, _undo: F)
+    where
+        F: FnOnce(&T) -> U,
+        U: FnOnce(&mut T) + 'static,
+        T: 'static,
+    {
+    }
+
+    /// Gets a mutable reference to the storage of type `T`
+    /// in the newest layer of the scope,
+    /// initializing it with `default` if it does not exist.
+    ///
+    /// Unlike [`Context::get_mut`],
+    /// the changes made through the reference are undone by [`Context::rollback`].
+    /// See [`Context::try_get_mut_journaled_at`] for details.
+    ///
+    /// # Panics
+    /// Panics if the scope is not in the stack.
+    fn get_mut_journaled<T, F>(&mut self, scope: TypeId, default: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+        T: Clone + 'static,
+    {
+        let storage = match self.find_layer(scope, LayerSelector::Nearest) {
+            Some(layer) => self.try_get_mut_journaled_at(layer, default),
+            None => None,
+        };
+        match storage {
+            Some(storage) => storage,
+            None => panic!("Attempt to fetch from scope {:?} which is not in the stack", scope),
+        }
+    }
+
     /// Pushes the type to the scope stack.
     ///
     /// This method is automatically called
     /// from [`Xylem::convert`].
     fn start_scope<T: 'static>(&mut self) -> Self::Scope;
 
+    /// Sets the path segment for the scope started by the next [`Context::start_scope`] call.
+    ///
+    /// This method is called before converting each field or element
+    /// so that errors can be annotated with the path to the failing value.
+    /// The default implementation discards the segment.
+    fn set_next_segment(&mut self, _segment: PathSegment) {}
+
+    /// Whether conversions should continue after a failure
+    /// to collect the errors of all fields.
+    ///
+    /// If this returns `true`, derived conversions and the standard collection conversions
+    /// convert all fields even if some of them fail,
+    /// and return all errors merged with [`AbstractError::merge`].
+    /// The default implementation returns `false`.
+    fn collect_errors(&self) -> bool { false }
+
+    /// Sets the source span of the value converted in the current scope.
+    ///
+    /// This method is called from the conversion of [`Spanned`].
+    /// The default implementation discards the span.
+    fn set_span(&mut self, _span: Range<usize>) {}
+
+    /// Returns the source span of the innermost scope with a span, if any.
+    ///
+    /// The default implementation always returns `None`.
+    fn span(&self) -> Option<Range<usize>> { None }
+
+    /// Returns the path from the root to the current scope,
+    /// consisting of the segments passed to [`Context::set_next_segment`].
+    ///
+    /// The default implementation always returns an empty path.
+    fn path(&self) -> FieldPath { FieldPath::default() }
+
+    /// Creates a checkpoint of the storage in the current scope stack.
+    ///
+    /// All changes made through [`Context::get_mut_journaled`]
+    /// and [`Context::try_get_mut_journaled_at`] after this call,
+    /// as well as the changes journaled with [`Context::journal_undo_at`],
+    /// can be undone by passing the checkpoint to [`Context::rollback`],
+    /// or kept by passing it to [`Context::commit`].
+    ///
+    /// Each checkpoint must be resolved by exactly one of these calls
+    /// while the scope stack is the same as when the checkpoint was created.
+    /// Nested checkpoints must be resolved in reverse order of creation.
+    ///
+    /// The default implementation does not support rollback,
+    /// i.e. [`Context::rollback`] keeps all changes.
+    /// Contexts that override this method should also override
+    /// [`Context::rollback`], [`Context::commit`], [`Context::try_get_mut_journaled_at`]
+    /// and [`Context::journal_undo_at`].
+    fn checkpoint(&mut self) -> Checkpoint { Checkpoint::new(0) }
+
+    /// Undoes all changes to the journaled storage since the checkpoint was created.
+    ///
+    /// The default implementation does nothing.
+    fn rollback(&mut self, _checkpoint: Checkpoint) {}
+
+    /// Keeps all changes to the storage since the checkpoint was created.
+    ///
+    /// If the checkpoint is nested in another checkpoint,
+    /// the changes can still be undone by rolling back the outer checkpoint.
+    ///
+    /// The default implementation does nothing.
+    fn commit(&mut self, _checkpoint: Checkpoint) {}
+
+    /// Runs `f` in a checkpoint,
+    /// committing the changes if it returns `Ok`
+    /// and rolling them back if it returns `Err`.
+    ///
+    /// This is useful for speculative conversions,
+    /// e.g. a failed attempt to convert a value does not declare any IDs.
+    /// The changes are also rolled back if `f` panics,
+    /// so the context can be reused after the panic is caught.
+    fn transaction<T, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, E>) -> Result<T, E> {
+        let checkpoint = self.checkpoint();
+        let mut guard = TransactionGuard { context: self, checkpoint: Some(checkpoint) };
+        let ret = f(guard.context);
+        let checkpoint = guard.checkpoint.take().expect("checkpoint is only resolved here");
+        match ret {
+            Ok(_) => guard.context.commit(checkpoint),
+            Err(_) => guard.context.rollback(checkpoint),
+        }
+        ret
+    }
+
+    /// Reports a non-fatal problem at the current path.
+    ///
+    /// Warnings are stored in the root scope,
+    /// so they are kept after the conversion
+    /// until they are retrieved with [`Context::take_warnings`].
+    /// Warnings reported in a checkpoint are discarded on rollback.
+    fn warn(&mut self, message: impl fmt::Display) {
+        let warning = Warning::new(self.path(), message.to_string());
+        diagnostics::report(self, [warning]);
+    }
+
+    /// Returns the warnings reported so far.
+    fn warnings(&self) -> &[Warning] {
+        match self.get::<diagnostics::Diagnostics>(TypeId::of::<()>()) {
+            Some(diagnostics) => &diagnostics.warnings,
+            None => &[],
+        }
+    }
+
+    /// Removes and returns the warnings reported so far.
+    fn take_warnings(&mut self) -> Vec<Warning> {
+        match self.get::<diagnostics::Diagnostics>(TypeId::of::<()>()) {
+            Some(diagnostics) if !diagnostics.warnings.is_empty() => diagnostics::take(self),
+            _ => Vec::new(),
+        }
+    }
+
     /// Pops a type from the scope stack.
     ///
     /// This method is automatically called
-    /// from [`Xylem::convert`].
+    /// from [`Xylem::convert`],
+    /// including when the conversion fails or panics.
     fn end_scope(&mut self, scope: Self::Scope);
+
+    /// Pops a type from the scope stack,
+    /// with the outcome of the conversion in the scope.
+    ///
+    /// This method is called instead of [`Context::end_scope`]
+    /// from [`Xylem::convert`] and [`XylemInverse::unconvert`].
+    /// The default implementation ignores the outcome and calls [`Context::end_scope`].
+    fn end_scope_with_outcome(&mut self, scope: Self::Scope, _outcome: ScopeOutcome) {
+        self.end_scope(scope)
+    }
+}
+
+/// Selects a layer among the layers of the same type in the scope stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerSelector {
+    /// The newest layer of the type, i.e. the nearest enclosing one.
+    Nearest,
+    /// The oldest layer of the type, i.e. the outermost one.
+    Outermost,
+    /// The `n`th newest layer of the type.
+    /// `Nth(0)` is equivalent to `Nearest`.
+    Nth(usize),
+    /// The newest layer of the type strictly below the layer at the index.
+    Below(usize),
+}
+
+/// Identifies a checkpoint created by [`Context::checkpoint`].
+///
+/// The index is opaque to the users of the context,
+/// and is only interpreted by the context that created the checkpoint.
+#[derive(Debug)]
+pub struct Checkpoint {
+    index: usize,
+}
+
+impl Checkpoint {
+    /// Creates a checkpoint with an index defined by the context.
+    pub fn new(index: usize) -> Self { Self { index } }
+
+    /// Returns the index passed to [`Checkpoint::new`].
+    pub fn index(&self) -> usize { self.index }
+}
+
+/// Rolls back the checkpoint of a [`Context::transaction`] on drop
+/// if it was not resolved, i.e. if the transaction panicked.
+struct TransactionGuard<'t, C: Context> {
+    context:    &'t mut C,
+    checkpoint: Option<Checkpoint>,
+}
+
+impl<'t, C: Context> Drop for TransactionGuard<'t, C> {
+    fn drop(&mut self) {
+        if let Some(checkpoint) = self.checkpoint.take() {
+            self.context.rollback(checkpoint);
+        }
+    }
+}
+
+/// Returns the number of layers in the stack of the context.
+fn stack_depth<C: Context>(context: &C) -> usize {
+    (0..).take_while(|&n| context.nth_last_scope(n).is_some()).count()
+}
+
+/// Returns the type ID of the layer at the index
+/// if it is the newest layer of its type.
+fn newest_scope_at<C: Context>(context: &C, layer: usize) -> Option<TypeId> {
+    let scope = context.nth_last_scope(stack_depth(context).checked_sub(layer + 1)?)?;
+    (context.find_layer(scope, LayerSelector::Nearest) == Some(layer)).then_some(scope)
 }
 
 /// The default empty argument type.
@@ -409,8 +1021,34 @@ pub use typemap_context::DefaultContext;
 /// #[xylem(schema = MySchema)]
 /// struct Foo {}
 /// ```
+///
+/// Use `extends` to declare a schema that inherits another schema
+/// with the same context and error types.
+/// See [`InheritSchema`] for details.
+///
+/// ```
+/// xylem::declare_schema!(Base: xylem::SchemaExt);
+/// xylem::declare_schema!(Child: extends Base, xylem::SchemaExt);
+/// ```
 #[macro_export]
 macro_rules! declare_schema {
+    ($(#[$meta:meta])* $vis:vis $name:ident: extends $parent:ty $(, $traits:path)* $(,)?) => {
+        $(#[$meta])*
+        $vis enum $name {}
+
+        impl $crate::Schema for $name {
+            type Context = <$parent as $crate::Schema>::Context;
+            type Error = <$parent as $crate::Schema>::Error;
+        }
+
+        impl $crate::InheritSchema for $name {
+            type Parent = $parent;
+        }
+
+        $(
+            impl $traits for $name {}
+        )*
+    };
     ($(#[$meta:meta])* $vis:vis $name:ident $(: $($traits:path),+)?) => {
         $(#[$meta])*
         $vis enum $name {}
@@ -425,3 +1063,147 @@ macro_rules! declare_schema {
         )*)?
     }
 }
+
+/// Delegates the conversion of types to the parent schema of an [`InheritSchema`].
+///
+/// The [`Xylem`] implementation for the schema calls the [`Xylem::convert_impl`]
+/// of the parent schema with the same `From` and `Args` types.
+/// Append `; inverse` to also delegate [`XylemInverse`].
+///
+/// # Example
+/// ```
+/// use xylem::{declare_schema, inherit, Id, Identifiable, Xylem};
+///
+/// declare_schema!(Base: xylem::SchemaExt);
+/// declare_schema!(Child: extends Base, xylem::SchemaExt);
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Base, inverse)]
+/// struct Item {
+///     #[xylem(args(new = true, track = true))]
+///     id: Id<Base, Item>,
+/// }
+///
+/// impl Identifiable<Base> for Item {
+///     type Scope = ();
+///     fn id(&self) -> Id<Base, Item> { self.id }
+/// }
+///
+/// // non-generic types can be delegated in one invocation, e.g. `Child: Foo, Bar`
+/// inherit!(Child: Item; inverse);
+/// // generic types are delegated one by one
+/// inherit!(impl<X: Identifiable<Base>> Child: Id<Base, X>; inverse);
+/// ```
+///
+/// # Nested overrides
+/// An inherited type is converted entirely under the parent schema,
+/// including all of its fields.
+/// If the child schema overrides the conversion of a field type,
+/// the override is not used for the fields of inherited types.
+/// To use the override, implement or derive the conversion of the outer type
+/// for the child schema instead of inheriting it.
+///
+/// ```
+/// use xylem::{declare_schema, inherit, DefaultContext, NoArgs, Xylem};
+///
+/// declare_schema!(Base: xylem::SchemaExt);
+/// declare_schema!(Child: extends Base, xylem::SchemaExt);
+///
+/// struct Level(u32);
+///
+/// impl Xylem<Base> for Level {
+///     type From = u32;
+///     type Args = NoArgs;
+///
+///     fn convert_impl(
+///         from: u32,
+///         _: &mut DefaultContext,
+///         _: &NoArgs,
+///     ) -> Result<Self, anyhow::Error> {
+///         Ok(Level(from))
+///     }
+/// }
+///
+/// // the child schema counts levels from 0 instead of 1
+/// impl Xylem<Child> for Level {
+///     type From = u32;
+///     type Args = NoArgs;
+///
+///     fn convert_impl(
+///         from: u32,
+///         _: &mut DefaultContext,
+///         _: &NoArgs,
+///     ) -> Result<Self, anyhow::Error> {
+///         Ok(Level(from + 1))
+///     }
+/// }
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Base, expose = MonsterFrom)]
+/// struct Monster {
+///     level: Level,
+/// }
+///
+/// inherit!(Child: Monster);
+///
+/// let mut context = DefaultContext::default();
+/// let level = <Level as Xylem<Child>>::convert(0, &mut context, &NoArgs).unwrap();
+/// assert_eq!(level.0, 1);
+/// // the field of the inherited type still uses the conversion of `Base`
+/// let monster = <Monster as Xylem<Child>>::convert(
+///     MonsterFrom { level: 0 },
+///     &mut context,
+///     &NoArgs,
+/// )
+/// .unwrap();
+/// assert_eq!(monster.level.0, 0);
+/// ```
+#[macro_export]
+macro_rules! inherit {
+    (impl<$($generic:ident $(: $bound:path)?),+ $(,)?> $schema:ty: $ty:ty $(; $flag:ident)?) => {
+        $crate::inherit!(@impl [$($generic $(: $bound)?),+] $schema, $ty $(, $flag)?);
+    };
+    ($schema:ty: $($ty:ty),+ $(,)?) => {
+        $(
+            $crate::inherit!(@impl [] $schema, $ty);
+        )+
+    };
+    ($schema:ty: $($ty:ty),+ $(,)?; inverse) => {
+        $(
+            $crate::inherit!(@impl [] $schema, $ty, inverse);
+        )+
+    };
+    (@impl [$($generics:tt)*] $schema:ty, $ty:ty) => {
+        impl<$($generics)*> $crate::Xylem<$schema> for $ty {
+            type From = <$ty as $crate::Xylem<<$schema as $crate::InheritSchema>::Parent>>::From;
+            type Args = <$ty as $crate::Xylem<<$schema as $crate::InheritSchema>::Parent>>::Args;
+
+            #[inline]
+            fn convert_impl(
+                from: Self::From,
+                context: &mut <$schema as $crate::Schema>::Context,
+                args: &Self::Args,
+            ) -> Result<Self, <$schema as $crate::Schema>::Error> {
+                <$ty as $crate::Xylem<<$schema as $crate::InheritSchema>::Parent>>::convert_impl(
+                    from, context, args,
+                )
+            }
+        }
+    };
+    (@impl [$($generics:tt)*] $schema:ty, $ty:ty, inverse) => {
+        $crate::inherit!(@impl [$($generics)*] $schema, $ty);
+
+        impl<$($generics)*> $crate::XylemInverse<$schema> for $ty {
+            #[inline]
+            fn unconvert_impl(
+                self,
+                context: &mut <$schema as $crate::Schema>::Context,
+                args: &Self::Args,
+            ) -> Result<Self::From, <$schema as $crate::Schema>::Error> {
+                <$ty as $crate::XylemInverse<<$schema as $crate::InheritSchema>::Parent>>::unconvert_impl(
+                    self, context, args,
+                )
+            }
+        }
+    };
+}
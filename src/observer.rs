@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use getset::CopyGetters;
+
+/// How a scope ended,
+/// passed to [`Context::end_scope_with_outcome`](crate::Context::end_scope_with_outcome).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeOutcome {
+    /// The conversion succeeded.
+    Success,
+    /// The conversion returned an error.
+    Failure,
+    /// The conversion panicked.
+    Panic,
+}
+
+/// Observes the scopes started and ended in a [`DefaultContext`](crate::DefaultContext),
+/// e.g. for logging, tracing or profiling.
+///
+/// Register observers with [`DefaultContext::add_observer`](crate::DefaultContext::add_observer).
+///
+/// With the `tracing` feature, `TracingObserver` enters a `tracing` span for each conversion.
+pub trait ScopeObserver {
+    /// Called after a scope is started.
+    fn start_scope(&mut self, _event: &ScopeStart) {}
+
+    /// Called after a scope is ended.
+    fn end_scope(&mut self, _event: &ScopeEnd) {}
+}
+
+/// A scope that has just started.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct ScopeStart {
+    /// The name of the type that the scope is started for.
+    #[getset(get_copy = "pub")]
+    pub(crate) type_name: &'static str,
+    /// The number of scopes below this scope, excluding the root scope.
+    #[getset(get_copy = "pub")]
+    pub(crate) depth:     usize,
+}
+
+/// A scope that has just ended.
+#[derive(Debug, Clone, CopyGetters)]
+pub struct ScopeEnd {
+    /// The name of the type that the scope was started for.
+    #[getset(get_copy = "pub")]
+    pub(crate) type_name: &'static str,
+    /// The number of scopes below this scope, excluding the root scope.
+    #[getset(get_copy = "pub")]
+    pub(crate) depth:     usize,
+    /// How the scope ended.
+    #[getset(get_copy = "pub")]
+    pub(crate) outcome:   ScopeOutcome,
+    /// The time elapsed between the start and the end of the scope.
+    #[getset(get_copy = "pub")]
+    pub(crate) duration:  Duration,
+}
+
+/// A [`ScopeObserver`] that enters a `tracing` span for each conversion.
+///
+/// Each span is named `convert` and has the fields `ty` and `depth`
+/// from [`ScopeStart`].
+/// When the scope ends, a `TRACE` event with the `outcome` and `duration`
+/// from [`ScopeEnd`] is emitted in the span before it is exited.
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+pub struct TracingObserver {
+    spans: Vec<tracing::span::EnteredSpan>,
+}
+
+#[cfg(feature = "tracing")]
+impl ScopeObserver for TracingObserver {
+    fn start_scope(&mut self, event: &ScopeStart) {
+        let span = tracing::trace_span!("convert", ty = event.type_name(), depth = event.depth());
+        self.spans.push(span.entered());
+    }
+
+    fn end_scope(&mut self, event: &ScopeEnd) {
+        tracing::trace!(outcome = ?event.outcome(), duration = ?event.duration());
+        self.spans.pop();
+    }
+}
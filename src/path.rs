@@ -0,0 +1,129 @@
+use std::fmt;
+#[cfg(feature = "anyhow")]
+use std::ops::Range;
+
+#[cfg(feature = "anyhow")]
+use getset::Getters;
+
+/// A segment in a [`FieldPath`].
+///
+/// Each segment describes how a value is reached from its parent value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// A field in a struct.
+    ///
+    /// Fields of tuple structs are named by their position.
+    Field(&'static str),
+    /// A field in an enum variant, identified by the variant name and the field name.
+    VariantField(&'static str, &'static str),
+    /// An element in a sequence or the value of an entry in a map, identified by its position.
+    Index(usize),
+    /// The key of an entry in a map, identified by the position of the entry.
+    MapKey(usize),
+}
+
+/// The path from the root value to the value currently being converted,
+/// e.g. `world.zones[3].spawns[1].monster`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldPath {
+    segments: Vec<PathSegment>,
+}
+
+impl FieldPath {
+    /// Creates a path from its segments, starting from the root.
+    pub fn new(segments: Vec<PathSegment>) -> Self { Self { segments } }
+
+    /// Appends a segment to the path.
+    pub fn child(mut self, segment: PathSegment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    /// Returns the segments of the path, starting from the root.
+    pub fn segments(&self) -> &[PathSegment] { &self.segments }
+
+    /// Returns whether the path refers to the root value.
+    pub fn is_empty(&self) -> bool { self.segments.is_empty() }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Field(field) => {
+                    if i > 0 {
+                        write!(f, ".")?;
+                    }
+                    write!(f, "{}", field)?;
+                }
+                PathSegment::VariantField(variant, field) => {
+                    if i > 0 {
+                        write!(f, "::")?;
+                    }
+                    write!(f, "{}.{}", variant, field)?;
+                }
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::MapKey(index) => write!(f, "[key {}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An [`anyhow::Error`] annotated with the path of the value that failed to convert
+/// and the source spans that the error refers to.
+#[cfg(feature = "anyhow")]
+#[derive(Debug, Getters)]
+pub struct PathError {
+    /// The path of the value that failed to convert.
+    #[getset(get = "pub")]
+    path:  FieldPath,
+    /// The source spans that the error refers to,
+    /// e.g. the declarations of a duplicate ID,
+    /// which can be located with [`LineColumn::of`](crate::LineColumn::of).
+    #[getset(get = "pub")]
+    spans: Vec<Range<usize>>,
+    /// The underlying error.
+    #[getset(get = "pub")]
+    error: anyhow::Error,
+}
+
+#[cfg(feature = "anyhow")]
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            return write!(f, "{}", &self.error);
+        }
+        write!(f, "{}: {}", &self.path, &self.error)
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl std::error::Error for PathError {}
+
+#[cfg(feature = "anyhow")]
+impl PathError {
+    pub(crate) fn wrap(mut error: anyhow::Error, path: FieldPath) -> anyhow::Error {
+        if path.is_empty() {
+            return error;
+        }
+        if let Some(err) = error.downcast_mut::<PathError>() {
+            if err.path.is_empty() {
+                err.path = path;
+            }
+            return error;
+        }
+        anyhow::Error::new(PathError { path, spans: Vec::new(), error })
+    }
+
+    pub(crate) fn with_spans(mut error: anyhow::Error, spans: Vec<Range<usize>>) -> anyhow::Error {
+        if spans.is_empty() {
+            return error;
+        }
+        if let Some(err) = error.downcast_mut::<PathError>() {
+            err.spans.extend(spans);
+            return error;
+        }
+        anyhow::Error::new(PathError { path: FieldPath::default(), spans, error })
+    }
+}
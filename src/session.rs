@@ -0,0 +1,175 @@
+use std::any::TypeId;
+
+#[cfg(feature = "id")]
+use crate::id::{self, Id, IdDiff, IdRegistry, IdTypes, KeyedIdentifiable};
+use crate::{finalize, Context, Schema, Warning, Xylem};
+
+/// A one-call entry point to convert a root value.
+///
+/// A session owns a fresh context,
+/// optionally preloaded with user data in the root scope.
+/// [`Session::convert`] converts the root value,
+/// calls the root [finalizers](crate::finalize),
+/// and returns the value with a [`Report`]
+/// that can be queried for the IDs and warnings produced by the conversion.
+///
+/// # Example
+/// ```
+/// use xylem::{Id, Identifiable, Session, Xylem};
+///
+/// xylem::declare_schema!(Schema: xylem::SchemaExt);
+///
+/// #[derive(Xylem)]
+/// #[xylem(schema = Schema, expose = ZoneFrom)]
+/// struct Zone {
+///     #[xylem(args(new = true))]
+///     id: Id<Schema, Zone>,
+/// }
+///
+/// impl Identifiable<Schema> for Zone {
+///     type Scope = ();
+///     fn id(&self) -> Id<Schema, Zone> { self.id }
+/// }
+///
+/// let (zones, report) = Session::<Schema>::new()
+///     .convert::<Vec<Zone>>(vec![
+///         ZoneFrom { id: String::from("forest") },
+///         ZoneFrom { id: String::from("cave") },
+///     ])
+///     .unwrap();
+/// assert_eq!(report.name(zones[1].id).map(String::as_str), Some("cave"));
+/// // neither zone is referenced
+/// assert_eq!(report.warnings()[0].to_string(), "[0].id: Unused ID forest");
+/// ```
+pub struct Session<S: Schema> {
+    context: S::Context,
+}
+
+impl<S: Schema> Default for Session<S> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<S: Schema> Session<S> {
+    /// Creates a session with a default context.
+    pub fn new() -> Self { Self { context: Default::default() } }
+
+    /// Creates a session with an existing context,
+    /// e.g. a context with custom options or observers.
+    pub fn with_context(context: S::Context) -> Self { Self { context } }
+
+    /// Preloads user data into the root scope,
+    /// replacing the previous value of the same type.
+    ///
+    /// Conversions can access the data with
+    /// `context.get::<T>(TypeId::of::<()>())`.
+    pub fn with_data<T: 'static>(mut self, data: T) -> Self {
+        let mut data = Some(data);
+        let slot = self
+            .context
+            .get_mut::<T, _>(TypeId::of::<()>(), || data.take().expect("called only once"));
+        if let Some(data) = data {
+            *slot = data;
+        }
+        self
+    }
+
+    /// Converts the root value with the default arguments.
+    pub fn convert<T: Xylem<S>>(self, from: T::From) -> Result<(T, Report<S>), S::Error> {
+        self.convert_with_args(from, &Default::default())
+    }
+
+    /// Converts the root value with the given arguments.
+    pub fn convert_with_args<T: Xylem<S>>(
+        mut self,
+        from: T::From,
+        args: &T::Args,
+    ) -> Result<(T, Report<S>), S::Error> {
+        let value = T::convert(from, &mut self.context, args)?;
+        finalize::<S>(&mut self.context)?;
+
+        let warnings = self.context.take_warnings();
+        Ok((value, Report { context: self.context, warnings }))
+    }
+
+    /// Converts a changed root value
+    /// and compares its IDs with the report of a previous conversion.
+    ///
+    /// `I` is a tuple of the [`Identifiable`](id::Identifiable) types to compare,
+    /// e.g. `(Zone, Spawn)`,
+    /// where scope types should be listed before the types scoped in them.
+    /// See [`IdDiff`] for details.
+    #[cfg(feature = "id")]
+    pub fn reconvert<T: Xylem<S>, I: IdTypes<S>>(
+        self,
+        from: T::From,
+        previous: &Report<S>,
+    ) -> Result<(T, Report<S>, IdDiff), S::Error> {
+        let (value, report) = self.convert::<T>(from)?;
+
+        let mut diff = IdDiff::default();
+        I::compare(&mut diff, &previous.context, &report.context);
+        Ok((value, report, diff))
+    }
+}
+
+/// The state left by a [`Session`] conversion.
+pub struct Report<S: Schema> {
+    context:  S::Context,
+    warnings: Vec<Warning>,
+}
+
+impl<S: Schema> Report<S> {
+    /// Returns the warnings reported during the conversion.
+    pub fn warnings(&self) -> &[Warning] { &self.warnings }
+
+    /// Returns the user data of type `T` in the root scope,
+    /// including the data preloaded with [`Session::with_data`].
+    pub fn get<T: 'static>(&self) -> Option<&T> { self.context.get::<T>(TypeId::of::<()>()) }
+
+    /// Returns the keys of the `X` IDs declared in the root scope,
+    /// in declaration order (which is the index order unless the indices are hashed).
+    ///
+    /// This only contains global IDs, i.e. those where `X::Scope` is `()`.
+    /// Use [`Report::tracked`] for scoped IDs.
+    /// Keys reserved by a seeded [`IdTable`](crate::id::IdTable) are included
+    /// even if they were not declared.
+    #[cfg(feature = "id")]
+    pub fn names<X: KeyedIdentifiable<S>>(&self) -> &[Option<X::Key>] {
+        id::root_names::<S, X>(&self.context)
+    }
+
+    /// Returns the key of a global ID,
+    /// or `None` if it was not declared in the root scope.
+    #[cfg(feature = "id")]
+    pub fn name<X: KeyedIdentifiable<S>>(&self, id: Id<S, X>) -> Option<&X::Key> {
+        id::root_name(&self.context, id)
+    }
+
+    /// Returns the keys of the `X` IDs declared with `track = true`
+    /// in the `X::Scope` object with the ID path `parent`, in declaration order.
+    ///
+    /// The ID path contains the indices of the `X::Scope` object
+    /// and each of its ancestors, starting from the root.
+    /// It is empty for global IDs.
+    #[cfg(feature = "id")]
+    pub fn tracked<X: KeyedIdentifiable<S>>(&self, parent: &[usize]) -> &[Option<X::Key>] {
+        self.context
+            .get::<id::GlobalIdStore<S, X>>(TypeId::of::<()>())
+            .and_then(|store| store.ids().get(parent))
+            .map_or(&[], |names| names.as_slice())
+    }
+
+    /// Collects the keys of the `X` IDs into a registry,
+    /// which can outlive the report.
+    #[cfg(feature = "id")]
+    pub fn registry<X: KeyedIdentifiable<S>>(&self) -> IdRegistry<S, X> {
+        IdRegistry::from_context(&self.context)
+    }
+
+    /// Returns the context used for the conversion.
+    pub fn context(&self) -> &S::Context { &self.context }
+
+    /// Returns the context used for the conversion,
+    /// e.g. to convert more values that reference the IDs in this conversion.
+    pub fn into_context(self) -> S::Context { self.context }
+}
@@ -0,0 +1,180 @@
+#[cfg(feature = "serde")]
+use std::cell::Cell;
+use std::fmt;
+use std::ops::Range;
+
+use getset::CopyGetters;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Context, Schema, Xylem, XylemInverse};
+
+/// A value with the byte range it was parsed from in the source file.
+///
+/// The `From` type of `Spanned<T>` is `Spanned<T::From>`.
+/// During conversion, the span is recorded in the context with [`Context::set_span`],
+/// so the conversion of `T` (and its fields) can retrieve it with [`Context::span`],
+/// e.g. to report the location of duplicate or unknown IDs.
+/// Such errors carry the byte ranges of the spans
+/// (see [`AbstractError::with_spans`](crate::AbstractError::with_spans));
+/// use [`LineColumn::of`] to locate them in the source file.
+/// Empty spans are not recorded.
+///
+/// With the `serde` feature, `Spanned<T>` is deserialized
+/// through `serde_spanned::Spanned`,
+/// so the span is filled by deserializers that support it, such as `toml`.
+/// Deserializers without span support, such as `serde_json`,
+/// can only deserialize `Spanned<T>` within `xylem::deserialize_without_spans`,
+/// which deserializes the plain value with an empty span.
+/// Values from other sources can be converted from `serde_spanned::Spanned`
+/// or created with [`Spanned::new`].
+/// Only the value is serialized.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Spanned<T> {
+    value: T,
+    span:  Range<usize>,
+}
+
+impl<T> Spanned<T> {
+    /// Creates a spanned value.
+    pub fn new(value: T, span: Range<usize>) -> Self { Self { value, span } }
+
+    /// Returns a reference to the value.
+    pub fn value(&self) -> &T { &self.value }
+
+    /// Returns a mutable reference to the value.
+    pub fn value_mut(&mut self) -> &mut T { &mut self.value }
+
+    /// Returns the byte range of the value in the source file.
+    pub fn span(&self) -> Range<usize> { self.span.clone() }
+
+    /// Discards the span and returns the value.
+    pub fn into_value(self) -> T { self.value }
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<serde_spanned::Spanned<T>> for Spanned<T> {
+    fn from(spanned: serde_spanned::Spanned<T>) -> Self {
+        let span = spanned.span();
+        Self { value: spanned.into_inner(), span }
+    }
+}
+
+#[cfg(feature = "serde")]
+thread_local! {
+    /// Whether [`deserialize_without_spans`] is running on this thread.
+    static WITHOUT_SPANS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with [`Spanned`] values deserialized as plain values with an empty span.
+///
+/// Serde cannot tell whether a deserializer supports spans,
+/// so deserializers without span support, such as `serde_json`,
+/// must be called within this function to deserialize `Spanned<T>`.
+///
+/// # Example
+/// ```
+/// use xylem::Spanned;
+///
+/// let value: Spanned<u32> =
+///     xylem::deserialize_without_spans(|| serde_json::from_str("3")).unwrap();
+/// assert_eq!(*value.value(), 3);
+/// assert_eq!(value.span(), 0..0);
+/// ```
+#[cfg(feature = "serde")]
+pub fn deserialize_without_spans<R>(f: impl FnOnce() -> R) -> R {
+    /// Restores the previous mode, even if `f` panics.
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) { WITHOUT_SPANS.with(|flag| flag.set(self.0)); }
+    }
+
+    let _restore = Restore(WITHOUT_SPANS.with(|flag| flag.replace(true)));
+    f()
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Spanned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if WITHOUT_SPANS.with(Cell::get) {
+            return T::deserialize(deserializer).map(|value| Self { value, span: 0..0 });
+        }
+        serde_spanned::Spanned::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for Spanned<T> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<S: Schema, T: Xylem<S>> Xylem<S> for Spanned<T> {
+    type From = Spanned<T::From>;
+    type Args = T::Args;
+
+    #[inline]
+    fn convert_impl(
+        from: Self::From,
+        context: &mut <S as Schema>::Context,
+        args: &Self::Args,
+    ) -> Result<Self, <S as Schema>::Error> {
+        if !from.span.is_empty() {
+            context.set_span(from.span.clone());
+        }
+        let value = T::convert(from.value, context, args)?;
+        Ok(Self { value, span: from.span })
+    }
+}
+
+/// The span is preserved in the inverse conversion.
+impl<S: Schema, T: XylemInverse<S>> XylemInverse<S> for Spanned<T> {
+    #[inline]
+    fn unconvert_impl(
+        self,
+        context: &mut <S as Schema>::Context,
+        args: &Self::Args,
+    ) -> Result<Self::From, <S as Schema>::Error> {
+        let value = self.value.unconvert(context, args)?;
+        Ok(Spanned { value, span: self.span })
+    }
+}
+
+/// A position in a source file, with 1-based line and column numbers.
+///
+/// Columns are counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, CopyGetters)]
+pub struct LineColumn {
+    /// The 1-based line number.
+    #[getset(get_copy = "pub")]
+    line:   usize,
+    /// The 1-based column number.
+    #[getset(get_copy = "pub")]
+    column: usize,
+}
+
+impl LineColumn {
+    /// Locates the byte offset in the source,
+    /// e.g. the start of a [`Spanned::span`].
+    ///
+    /// Offsets past the end of the source are located at the end of the source.
+    ///
+    /// # Panics
+    /// Panics if the offset is not on a character boundary.
+    pub fn of(source: &str, offset: usize) -> Self {
+        let before = &source[..offset.min(source.len())];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Self {
+            line:   before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for LineColumn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
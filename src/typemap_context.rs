@@ -1,9 +1,15 @@
-use std::any::TypeId;
+use std::any::{type_name, TypeId};
+use std::collections::HashSet;
 use std::marker::PhantomData;
+use std::ops::Range;
+use std::time::Instant;
 
 use typemap::TypeMap;
 
-use super::Context;
+use super::{
+    Checkpoint, Context, FieldPath, LayerSelector, PathSegment, ScopeEnd, ScopeObserver,
+    ScopeOutcome, ScopeStart,
+};
 
 struct TypeMapKey<T: 'static>(PhantomData<T>);
 
@@ -12,14 +18,53 @@ impl<T> typemap::Key for TypeMapKey<T> {
 }
 
 /// A [`Context`] implementation based on [`typemap::TypeMap`].
+///
+/// Checkpoints are implemented with a journal:
+/// the first time a storage is accessed with [`Context::try_get_mut_journaled_at`]
+/// after a checkpoint,
+/// its previous value is cloned so that it can be restored on rollback.
+/// The undo entries passed to [`Context::journal_undo_at`]
+/// are kept in the same journal and called in reverse order on rollback.
 pub struct DefaultContext {
-    layers: Vec<Layer>,
+    layers:         Vec<Layer>,
+    next_segment:   Option<PathSegment>,
+    collect_errors: bool,
+    journals:       Vec<Journal>,
+    observers:      Vec<Box<dyn ScopeObserver>>,
+}
+
+impl DefaultContext {
+    /// Sets whether conversions should collect the errors of all fields
+    /// instead of failing on the first error.
+    ///
+    /// See [`Context::collect_errors`] for details.
+    pub fn set_collect_errors(&mut self, collect_errors: bool) {
+        self.collect_errors = collect_errors;
+    }
+
+    /// Adds an observer that is notified when scopes start and end.
+    ///
+    /// Observers are notified in the order they are added.
+    pub fn add_observer(&mut self, observer: impl ScopeObserver + 'static) {
+        self.observers.push(Box::new(observer));
+    }
 }
 
 impl Default for DefaultContext {
     fn default() -> Self {
         DefaultContext {
-            layers: vec![Layer { type_id: TypeId::of::<()>(), map: TypeMap::custom() }],
+            layers:         vec![Layer {
+                type_id:   TypeId::of::<()>(),
+                type_name: type_name::<()>(),
+                map:       TypeMap::custom(),
+                segment:   None,
+                span:      None,
+                started:   None,
+            }],
+            next_segment:   None,
+            collect_errors: false,
+            journals:       Vec::new(),
+            observers:      Vec::new(),
         }
     }
 }
@@ -31,27 +76,97 @@ impl Context for DefaultContext {
         let type_id = TypeId::of::<T>();
         let index = self.layers.len();
 
-        self.layers.push(Layer { type_id, map: TypeMap::custom() });
+        let segment = self.next_segment.take();
+        let type_name = type_name::<T>();
+        // avoid querying the clock if nobody observes the duration
+        let started = (!self.observers.is_empty()).then(Instant::now);
+        self.layers.push(Layer {
+            type_id,
+            type_name,
+            map: TypeMap::custom(),
+            segment,
+            span: None,
+            started,
+        });
+
+        let event = ScopeStart { type_name, depth: index - 1 };
+        for observer in &mut self.observers {
+            observer.start_scope(&event);
+        }
 
         Scope { type_id, index }
     }
 
     fn end_scope(&mut self, scope: Scope) {
+        self.end_scope_with_outcome(scope, ScopeOutcome::Success)
+    }
+
+    fn end_scope_with_outcome(&mut self, scope: Scope, outcome: ScopeOutcome) {
+        // discard the segment set by a field that failed before starting its scope
+        self.next_segment = None;
+
         let layer = self.layers.pop().expect("Ending scope of empty layout");
         debug_assert_eq!(scope.type_id, layer.type_id, "Scope mismatch");
         debug_assert_eq!(scope.index, self.layers.len(), "Scope mismatch");
+
+        if !self.observers.is_empty() {
+            let event = ScopeEnd {
+                type_name: layer.type_name,
+                depth: scope.index - 1,
+                outcome,
+                duration: layer.started.map(|started| started.elapsed()).unwrap_or_default(),
+            };
+            for observer in &mut self.observers {
+                observer.end_scope(&event);
+            }
+        }
+    }
+
+    fn set_next_segment(&mut self, segment: PathSegment) { self.next_segment = Some(segment); }
+
+    fn collect_errors(&self) -> bool { self.collect_errors }
+
+    fn set_span(&mut self, span: Range<usize>) {
+        self.layers.last_mut().expect("Scope stack is never empty").span = Some(span);
+    }
+
+    fn span(&self) -> Option<Range<usize>> {
+        self.layers.iter().rev().find_map(|layer| layer.span.clone())
+    }
+
+    fn path(&self) -> FieldPath {
+        FieldPath::new(self.layers.iter().filter_map(|layer| layer.segment.clone()).collect())
     }
 
     fn nth_last_scope(&self, n: usize) -> Option<TypeId> {
-        self.layers.get(self.layers.len() - n - 1).map(|layer| layer.type_id)
+        let index = self.layers.len().checked_sub(n)?.checked_sub(1)?;
+        Some(self.layers[index].type_id)
     }
 
-    fn get<T>(&self, scope: TypeId) -> Option<&T>
+    fn find_layer(&self, scope: TypeId, selector: LayerSelector) -> Option<usize> {
+        let matches = |layer: &Layer| layer.type_id == scope;
+        match selector {
+            LayerSelector::Nearest => self.layers.iter().rposition(matches),
+            LayerSelector::Outermost => self.layers.iter().position(matches),
+            LayerSelector::Nth(n) => self
+                .layers
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|(_, layer)| matches(layer))
+                .nth(n)
+                .map(|(index, _)| index),
+            LayerSelector::Below(index) => {
+                self.layers[..index.min(self.layers.len())].iter().rposition(matches)
+            }
+        }
+    }
+
+    fn get_at<T>(&self, layer: usize) -> Option<&T>
     where
         T: 'static,
     {
-        let layer = self.layers.iter().rev().find(|layer| layer.type_id == scope)?;
-        layer.map.get::<TypeMapKey<T>>()
+        self.layers.get(layer)?.map.get::<TypeMapKey<T>>()
     }
 
     #[inline]
@@ -62,22 +177,201 @@ impl Context for DefaultContext {
         Box::new(self.layers.iter().rev().filter_map(|layer| layer.map.get::<TypeMapKey<T>>()))
     }
 
+    fn get<T>(&self, scope: TypeId) -> Option<&T>
+    where
+        T: 'static,
+    {
+        self.get_at(self.find_layer(scope, LayerSelector::Nearest)?)
+    }
+
     fn get_mut<T, F>(&mut self, scope: TypeId, default: F) -> &mut T
     where
         F: FnOnce() -> T,
         T: 'static,
     {
-        let layer = match self.layers.iter_mut().rev().find(|layer| layer.type_id == scope) {
-            Some(layer) => layer,
-            None => panic!("Attempt to fetch from scope {:?} which is not in the stack", scope,),
+        match self.try_get_mut(scope, default) {
+            Some(storage) => storage,
+            None => panic!("Attempt to fetch from scope {:?} which is not in the stack", scope),
+        }
+    }
+
+    fn try_get_mut<T, F>(&mut self, scope: TypeId, default: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+        T: 'static,
+    {
+        let layer = self.find_layer(scope, LayerSelector::Nearest)?;
+        self.try_get_mut_at(layer, default)
+    }
+
+    fn try_get_mut_at<T, F>(&mut self, index: usize, default: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+        T: 'static,
+    {
+        let layer = self.layers.get_mut(index)?;
+        Some(layer.map.entry::<TypeMapKey<T>>().or_insert_with(default))
+    }
+
+    fn try_get_mut_journaled_at<T, F>(&mut self, index: usize, default: F) -> Option<&mut T>
+    where
+        F: FnOnce() -> T,
+        T: Clone + 'static,
+    {
+        let layer = self.layers.get_mut(index)?;
+
+        if let Some(journal) = self.journals.last_mut() {
+            journal.record::<T>(index, &layer.map);
+        }
+
+        Some(layer.map.entry::<TypeMapKey<T>>().or_insert_with(default))
+    }
+
+    fn journal_undo_at<T, F, U>(&mut self, index: usize, undo: F)
+    where
+        F: FnOnce(&T) -> U,
+        U: FnOnce(&mut T) + 'static,
+        T: 'static,
+    {
+        if let (Some(journal), Some(layer)) = (self.journals.last_mut(), self.layers.get(index)) {
+            journal.record_undo(index, layer.map.get::<TypeMapKey<T>>().map(undo));
+        }
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        let index = self.journals.len();
+        self.journals.push(Journal {
+            depth:    self.layers.len(),
+            recorded: HashSet::new(),
+            undo:     Vec::new(),
+        });
+        Checkpoint::new(index)
+    }
+
+    fn rollback(&mut self, checkpoint: Checkpoint) {
+        let journal = self.pop_journal(checkpoint);
+        for entry in journal.undo.into_iter().rev() {
+            (entry.undo)(&mut self.layers[entry.index].map);
+        }
+    }
+
+    fn commit(&mut self, checkpoint: Checkpoint) {
+        let journal = self.pop_journal(checkpoint);
+        if let Some(parent) = self.journals.last_mut() {
+            parent.merge(journal);
+        }
+    }
+}
+
+impl DefaultContext {
+    /// Pops the journal of the checkpoint,
+    /// committing the journals of unresolved nested checkpoints into it.
+    fn pop_journal(&mut self, checkpoint: Checkpoint) -> Journal {
+        assert!(checkpoint.index() < self.journals.len(), "Checkpoint resolved twice");
+        while self.journals.len() > checkpoint.index() + 1 {
+            let nested = self.journals.pop().expect("checked length");
+            self.journals.last_mut().expect("checked length").merge(nested);
+        }
+        let journal = self.journals.pop().expect("checked length");
+        debug_assert!(journal.depth <= self.layers.len(), "Checkpoint outlived its scope");
+        journal
+    }
+}
+
+type Undo = Box<dyn FnOnce(&mut TypeMap)>;
+
+/// Records the changes since a checkpoint.
+struct Journal {
+    /// The number of layers when the checkpoint was created.
+    ///
+    /// Layers pushed after the checkpoint are discarded before it is resolved,
+    /// so their changes are not recorded.
+    depth:    usize,
+    /// The storages that already have a snapshot, identified by layer index and type.
+    recorded: HashSet<(usize, TypeId)>,
+    undo:     Vec<UndoEntry>,
+}
+
+/// Undoes a change to the storage of the layer at `index`.
+struct UndoEntry {
+    index:    usize,
+    /// The type of the storage if the entry restores a snapshot of it,
+    /// or `None` for an entry passed to [`Context::journal_undo_at`].
+    snapshot: Option<TypeId>,
+    undo:     Undo,
+}
+
+impl Journal {
+    fn record<T: Clone + 'static>(&mut self, index: usize, map: &TypeMap) {
+        if index >= self.depth || !self.recorded.insert((index, TypeId::of::<T>())) {
+            return;
+        }
+
+        let undo: Undo = match map.get::<TypeMapKey<T>>() {
+            Some(value) => {
+                let value = value.clone();
+                Box::new(move |map| {
+                    map.insert::<TypeMapKey<T>>(value);
+                })
+            }
+            None => Box::new(|map| {
+                map.remove::<TypeMapKey<T>>();
+            }),
         };
-        layer.map.entry::<TypeMapKey<T>>().or_insert_with(default)
+        self.undo.push(UndoEntry { index, snapshot: Some(TypeId::of::<T>()), undo });
+    }
+
+    /// Records an entry passed to [`Context::journal_undo_at`],
+    /// or the removal of the storage if `undo` is `None` because it does not exist yet.
+    fn record_undo<T: 'static>(
+        &mut self,
+        index: usize,
+        undo: Option<impl FnOnce(&mut T) + 'static>,
+    ) {
+        if index >= self.depth {
+            return;
+        }
+
+        let undo: Undo = match undo {
+            Some(undo) => Box::new(move |map| {
+                if let Some(value) = map.get_mut::<TypeMapKey<T>>() {
+                    undo(value);
+                }
+            }),
+            None => Box::new(|map| {
+                map.remove::<TypeMapKey<T>>();
+            }),
+        };
+        self.undo.push(UndoEntry { index, snapshot: None, undo });
+    }
+
+    /// Moves the undo entries of a committed nested journal into this journal.
+    ///
+    /// Snapshots of storages already recorded in this journal are dropped,
+    /// because this journal has an older value to restore.
+    fn merge(&mut self, nested: Journal) {
+        for entry in nested.undo {
+            if entry.index >= self.depth {
+                continue;
+            }
+            if let Some(type_id) = entry.snapshot {
+                if !self.recorded.insert((entry.index, type_id)) {
+                    continue;
+                }
+            }
+            self.undo.push(entry);
+        }
     }
 }
 
 struct Layer {
-    type_id: TypeId,
-    map:     TypeMap,
+    type_id:   TypeId,
+    type_name: &'static str,
+    map:       TypeMap,
+    segment:   Option<PathSegment>,
+    span:      Option<Range<usize>>,
+    /// The time when the scope started, if there were observers.
+    started:   Option<Instant>,
 }
 
 /// Return value for [`DefaultContext::start_scope`].
@@ -0,0 +1,160 @@
+use xylem::{
+    declare_schema, finalize, Context, DefaultContext, Id, IdAliases, Identifiable, LazyId, NoArgs,
+    SchemaExt, Xylem, XylemInverse,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = CatalogFrom, inverse)]
+struct Catalog {
+    featured: LazyId<Schema, Item>,
+    items:    Vec<Item>,
+    recipes:  Vec<Recipe>,
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ItemFrom, inverse)]
+struct Item {
+    #[xylem(args(new = true))]
+    id:      Id<Schema, Item>,
+    aliases: IdAliases<Schema, Item>,
+}
+
+impl Identifiable<Schema> for Item {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Item> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = RecipeFrom, inverse)]
+struct Recipe {
+    output: Id<Schema, Item>,
+}
+
+#[test]
+fn test_alias_reference() {
+    let mut context = DefaultContext::default();
+    let catalog = Catalog::convert(
+        CatalogFrom {
+            featured: String::from("blade"),
+            items:    vec![
+                ItemFrom {
+                    id:      String::from("sword"),
+                    aliases: vec![String::from("blade"), String::from("sabre")],
+                },
+                ItemFrom { id: String::from("shield"), aliases: Vec::new() },
+            ],
+            recipes:  vec![
+                RecipeFrom { output: String::from("sabre") },
+                RecipeFrom { output: String::from("shield") },
+                RecipeFrom { output: String::from("sword") },
+            ],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    finalize::<Schema>(&mut context).unwrap();
+
+    let sword = catalog.items[0].id;
+    assert_eq!(catalog.featured.id(), sword);
+    assert_eq!(catalog.recipes[0].output, sword);
+    assert_eq!(catalog.recipes[1].output, catalog.items[1].id);
+    assert_eq!(catalog.recipes[2].output, sword);
+
+    let warnings: Vec<_> = context.warnings().iter().map(ToString::to_string).collect();
+    assert_eq!(
+        warnings,
+        [
+            "recipes[0].output: ID sabre is a deprecated alias of sword",
+            "featured: ID blade is a deprecated alias of sword",
+        ]
+    );
+}
+
+#[test]
+fn test_duplicate_alias() {
+    let mut context = DefaultContext::default();
+    let err = Catalog::convert(
+        CatalogFrom {
+            featured: String::from("sword"),
+            items:    vec![
+                ItemFrom { id: String::from("sword"), aliases: Vec::new() },
+                ItemFrom { id: String::from("shield"), aliases: vec![String::from("sword")] },
+            ],
+            recipes:  Vec::new(),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "items[1].aliases: Duplicate ID sword");
+
+    let mut context = DefaultContext::default();
+    let err = Catalog::convert(
+        CatalogFrom {
+            featured: String::from("sword"),
+            items:    vec![
+                ItemFrom { id: String::from("sword"), aliases: vec![String::from("blade")] },
+                ItemFrom { id: String::from("blade"), aliases: Vec::new() },
+            ],
+            recipes:  Vec::new(),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "items[1].id: Duplicate ID blade");
+}
+
+#[test]
+fn test_unconvert() {
+    let mut context = DefaultContext::default();
+    let catalog = Catalog::convert(
+        CatalogFrom {
+            featured: String::from("sword"),
+            items:    vec![ItemFrom {
+                id:      String::from("sword"),
+                aliases: vec![String::from("blade")],
+            }],
+            recipes:  vec![RecipeFrom { output: String::from("blade") }],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    finalize::<Schema>(&mut context).unwrap();
+
+    let from = catalog.unconvert(&mut context, &NoArgs).unwrap();
+    assert_eq!(from.items[0].aliases, ["blade"]);
+    // references are unconverted to the declared name
+    assert_eq!(from.recipes[0].output, "sword");
+}
+
+#[test]
+fn test_rollback_aliases() {
+    let mut context = DefaultContext::default();
+    let result = context.transaction(|context| {
+        Vec::<Item>::convert(
+            vec![ItemFrom { id: String::from("sword"), aliases: vec![String::from("blade")] }],
+            context,
+            &NoArgs,
+        )?;
+        Err::<(), _>(anyhow::anyhow!("rejected"))
+    });
+    assert!(result.is_err());
+
+    // the alias is no longer registered, so it can be declared as a name
+    let items = Vec::<Item>::convert(
+        vec![
+            ItemFrom { id: String::from("blade"), aliases: Vec::new() },
+            ItemFrom { id: String::from("sword"), aliases: Vec::new() },
+        ],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(items[0].id.index(), 0);
+    assert_eq!(items[1].id.index(), 1);
+}
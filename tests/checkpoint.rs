@@ -0,0 +1,230 @@
+use std::any::TypeId;
+use std::panic::{self, AssertUnwindSafe};
+
+use xylem::id::GlobalIdStore;
+use xylem::{declare_schema, Context, DefaultContext, Id, Identifiable, NoArgs, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Xylem)]
+#[xylem(expose = FooFrom)]
+struct Foo {
+    #[xylem(args(new = true))]
+    id:  Id<Schema, Foo>,
+    #[allow(dead_code)] // it's only used to provide type context.
+    bar: Vec<Bar>,
+}
+
+impl Identifiable<Schema> for Foo {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Foo> { self.id }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = BarFrom)]
+struct Bar {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Bar>,
+}
+
+impl Identifiable<Schema> for Bar {
+    type Scope = Foo;
+    fn id(&self) -> Id<Schema, Bar> { self.id }
+}
+
+fn tracked_bars(context: &DefaultContext) -> usize {
+    context
+        .get::<GlobalIdStore<Schema, Bar>>(TypeId::of::<()>())
+        .map_or(0, |store| store.ids().values().map(|names| names.len()).sum())
+}
+
+#[derive(Clone, Default)]
+struct Counter(u32);
+
+#[test]
+fn test_rollback_ids() {
+    let mut context = DefaultContext::default();
+
+    Foo::convert(
+        FooFrom { id: String::from("first"), bar: vec![BarFrom { id: String::from("a") }] },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    let checkpoint = context.checkpoint();
+    let second = Foo::convert(
+        FooFrom {
+            id:  String::from("second"),
+            bar: vec![BarFrom { id: String::from("b") }, BarFrom { id: String::from("c") }],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(second.id.index(), 1);
+    assert_eq!(tracked_bars(&context), 3);
+    context.rollback(checkpoint);
+
+    assert_eq!(tracked_bars(&context), 1);
+    let third =
+        Foo::convert(FooFrom { id: String::from("third"), bar: Vec::new() }, &mut context, &NoArgs)
+            .unwrap();
+    assert_eq!(third.id.index(), 1);
+    // "second" is no longer declared, so it can be declared again
+    let second = Foo::convert(
+        FooFrom { id: String::from("second"), bar: Vec::new() },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(second.id.index(), 2);
+}
+
+#[test]
+fn test_transaction() {
+    let mut context = DefaultContext::default();
+
+    let err = context
+        .transaction(|context| {
+            Foo::convert(
+                FooFrom {
+                    id:  String::from("first"),
+                    bar: vec![BarFrom { id: String::from("a") }],
+                },
+                context,
+                &NoArgs,
+            )?;
+            Foo::convert(FooFrom { id: String::from("first"), bar: Vec::new() }, context, &NoArgs)
+        })
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "id: Duplicate ID first");
+    assert_eq!(tracked_bars(&context), 0);
+
+    let foo = context
+        .transaction(|context| {
+            Foo::convert(
+                FooFrom {
+                    id:  String::from("first"),
+                    bar: vec![BarFrom { id: String::from("a") }],
+                },
+                context,
+                &NoArgs,
+            )
+        })
+        .unwrap();
+    assert_eq!(foo.id.index(), 0);
+    assert_eq!(tracked_bars(&context), 1);
+}
+
+#[test]
+fn test_transaction_panic() {
+    let mut context = DefaultContext::default();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _: Result<(), anyhow::Error> = context.transaction(|context| {
+            Foo::convert(
+                FooFrom {
+                    id:  String::from("first"),
+                    bar: vec![BarFrom { id: String::from("a") }],
+                },
+                context,
+                &NoArgs,
+            )?;
+            panic!("conversion panicked");
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(tracked_bars(&context), 0);
+
+    // the journal of the transaction is not left behind
+    let checkpoint = context.checkpoint();
+    assert_eq!(checkpoint.index(), 0);
+    context.rollback(checkpoint);
+
+    let foo =
+        Foo::convert(FooFrom { id: String::from("first"), bar: Vec::new() }, &mut context, &NoArgs)
+            .unwrap();
+    assert_eq!(foo.id.index(), 0);
+}
+
+#[test]
+fn test_nested_checkpoints() {
+    let mut context = DefaultContext::default();
+    let root = TypeId::of::<()>();
+
+    context.get_mut_journaled::<Counter, _>(root, Default::default).0 = 1;
+
+    let outer = context.checkpoint();
+    context.get_mut_journaled::<Counter, _>(root, Default::default).0 = 2;
+
+    let inner = context.checkpoint();
+    context.get_mut_journaled::<Counter, _>(root, Default::default).0 = 3;
+    context.get_mut_journaled::<String, _>(root, Default::default).push_str("new");
+    context.commit(inner);
+    assert_eq!(context.get::<Counter>(root).unwrap().0, 3);
+
+    let inner = context.checkpoint();
+    context.get_mut_journaled::<Counter, _>(root, Default::default).0 = 4;
+    context.rollback(inner);
+    assert_eq!(context.get::<Counter>(root).unwrap().0, 3);
+
+    context.rollback(outer);
+    assert_eq!(context.get::<Counter>(root).unwrap().0, 1);
+    assert!(context.get::<String>(root).is_none());
+}
+
+/// Storage that does not implement `Clone`.
+#[derive(Default)]
+struct Handle(u32);
+
+/// Storage that is journaled with undo entries.
+#[derive(Default)]
+struct Log(Vec<u32>);
+
+fn push_log(context: &mut DefaultContext, value: u32) {
+    context.journal_undo_at(0, |log: &Log| {
+        let len = log.0.len();
+        move |log: &mut Log| log.0.truncate(len)
+    });
+    context.try_get_mut_at::<Log, _>(0, Default::default).unwrap().0.push(value);
+}
+
+#[test]
+fn test_undo_entries() {
+    let mut context = DefaultContext::default();
+    let root = TypeId::of::<()>();
+
+    let outer = context.checkpoint();
+    push_log(&mut context, 1);
+
+    let inner = context.checkpoint();
+    push_log(&mut context, 2);
+    context.commit(inner);
+
+    let inner = context.checkpoint();
+    push_log(&mut context, 3);
+    push_log(&mut context, 4);
+    context.rollback(inner);
+    assert_eq!(context.get::<Log>(root).unwrap().0, [1, 2]);
+
+    // the storage did not exist at the checkpoint
+    context.rollback(outer);
+    assert!(context.get::<Log>(root).is_none());
+}
+
+#[test]
+fn test_unjournaled_storage() {
+    let mut context = DefaultContext::default();
+    let root = TypeId::of::<()>();
+
+    let checkpoint = context.checkpoint();
+    context.get_mut::<Handle, _>(root, Default::default).0 = 1;
+    context.get_mut_journaled::<Counter, _>(root, Default::default).0 = 1;
+    context.rollback(checkpoint);
+
+    // only journaled storage is rolled back
+    assert_eq!(context.get::<Handle>(root).unwrap().0, 1);
+    assert!(context.get::<Counter>(root).is_none());
+}
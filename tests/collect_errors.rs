@@ -0,0 +1,93 @@
+#![allow(dead_code)] // the types are only converted to test the errors.
+
+use std::collections::BTreeMap;
+
+use xylem::{declare_schema, DefaultContext, ErrorList, NoArgs, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+impl Xylem<Schema> for u32 {
+    type From = String;
+    type Args = NoArgs;
+
+    fn convert_impl(
+        from: Self::From,
+        _context: &mut DefaultContext,
+        _args: &Self::Args,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(from.parse()?)
+    }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = FooFrom)]
+struct Foo {
+    first:  u32,
+    second: Vec<u32>,
+    third:  BTreeMap<u32, u32>,
+    #[xylem(transform = parse_even(u32))]
+    fourth: u32,
+}
+
+fn parse_even(value: u32) -> anyhow::Result<u32> {
+    if !value.is_multiple_of(2) {
+        anyhow::bail!("{} is odd", value);
+    }
+    Ok(value)
+}
+
+fn foo_from() -> FooFrom {
+    FooFrom {
+        first:  String::from("a"),
+        second: vec![String::from("1"), String::from("b"), String::from("c")],
+        third:  [(String::from("2"), String::from("d"))].into_iter().collect(),
+        fourth: 3,
+    }
+}
+
+#[test]
+fn test_fail_fast() {
+    let mut context = DefaultContext::default();
+    let err = Foo::convert(foo_from(), &mut context, &NoArgs).err().unwrap();
+    assert_eq!(err.to_string(), "first: invalid digit found in string");
+}
+
+#[test]
+fn test_collect_errors() {
+    let mut context = DefaultContext::default();
+    context.set_collect_errors(true);
+
+    let err = Foo::convert(foo_from(), &mut context, &NoArgs).err().unwrap();
+    let list = err.downcast_ref::<ErrorList>().unwrap();
+    let messages: Vec<_> = list.errors().iter().map(|err| err.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "first: invalid digit found in string",
+            "second[1]: invalid digit found in string",
+            "second[2]: invalid digit found in string",
+            "third[0]: invalid digit found in string",
+            "fourth: 3 is odd",
+        ]
+    );
+}
+
+#[test]
+fn test_collect_no_errors() {
+    let mut context = DefaultContext::default();
+    context.set_collect_errors(true);
+
+    let foo = Foo::convert(
+        FooFrom {
+            first:  String::from("1"),
+            second: vec![String::from("2")],
+            third:  BTreeMap::new(),
+            fourth: 4,
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(foo.second, [2]);
+    assert_eq!(foo.fourth, 4);
+}
@@ -0,0 +1,143 @@
+use std::any::TypeId;
+use std::panic::{self, AssertUnwindSafe};
+
+use xylem::{
+    declare_schema, finalize, Context, DefaultContext, Id, Identifiable, LazyId, NoArgs, SchemaExt,
+    Xylem,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom)]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Zone>,
+    next:   Option<LazyId<Schema, Zone>>,
+    spawns: Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom)]
+struct Spawn {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Spawn>,
+    #[xylem(transform = parse_weight(String))]
+    weight: u32,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+}
+
+fn parse_weight(weight: String) -> anyhow::Result<u32> {
+    if weight == "panic" {
+        panic!("weight panicked");
+    }
+    Ok(weight.parse()?)
+}
+
+fn assert_root_only(context: &DefaultContext) {
+    assert_eq!(context.nth_last_scope(0), Some(TypeId::of::<()>()));
+    assert!(context.path().is_empty());
+}
+
+fn assert_reusable(context: &mut DefaultContext) {
+    let zone = Zone::convert(
+        ZoneFrom {
+            id:     String::from("cave"),
+            next:   None,
+            spawns: vec![
+                SpawnFrom { id: String::from("spawn0"), weight: String::from("1") },
+                SpawnFrom { id: String::from("spawn1"), weight: String::from("2") },
+            ],
+        },
+        context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(zone.id.index(), 1);
+    assert_eq!(zone.spawns[0].id.index(), 0);
+    assert_eq!(zone.spawns[1].id.index(), 1);
+    assert_eq!(zone.spawns[1].weight, 2);
+    assert_root_only(context);
+}
+
+#[test]
+fn test_reuse_after_error() {
+    let mut context = DefaultContext::default();
+
+    let err = Zone::convert(
+        ZoneFrom {
+            id:     String::from("forest"),
+            next:   None,
+            spawns: vec![
+                SpawnFrom { id: String::from("spawn0"), weight: String::from("1") },
+                SpawnFrom { id: String::from("spawn1"), weight: String::from("x") },
+                SpawnFrom { id: String::from("spawn2"), weight: String::from("3") },
+            ],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "spawns[1].weight: invalid digit found in string");
+    assert_root_only(&context);
+
+    assert_reusable(&mut context);
+}
+
+#[test]
+fn test_reuse_after_panic() {
+    let mut context = DefaultContext::default();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Zone::convert(
+            ZoneFrom {
+                id:     String::from("forest"),
+                next:   None,
+                spawns: vec![
+                    SpawnFrom { id: String::from("spawn0"), weight: String::from("1") },
+                    SpawnFrom { id: String::from("spawn1"), weight: String::from("panic") },
+                ],
+            },
+            &mut context,
+            &NoArgs,
+        )
+    }));
+    assert!(result.is_err());
+    assert_root_only(&context);
+
+    assert_reusable(&mut context);
+}
+
+#[test]
+fn test_discard_pending_after_error() {
+    let mut context = DefaultContext::default();
+
+    let forest = ZoneFrom {
+        id:     String::from("forest"),
+        next:   Some(String::from("ghost")),
+        spawns: vec![
+            SpawnFrom { id: String::from("spawn0"), weight: String::from("1") },
+            SpawnFrom { id: String::from("spawn1"), weight: String::from("x") },
+        ],
+    };
+    Zone::convert(forest, &mut context, &NoArgs).unwrap_err();
+    assert_root_only(&context);
+
+    let cave = ZoneFrom {
+        id:     String::from("cave"),
+        next:   Some(String::from("forest")),
+        spawns: vec![SpawnFrom { id: String::from("spawn0"), weight: String::from("1") }],
+    };
+    let cave = Zone::convert(cave, &mut context, &NoArgs).unwrap();
+    finalize::<Schema>(&mut context).unwrap();
+    assert_eq!(cave.next.unwrap().id().index(), 0);
+}
@@ -0,0 +1,129 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use xylem::{Context, Id, Identifiable, NoArgs, SchemaExt, Xylem};
+
+/// The storage of a layer, keyed by the type ID of the storage type.
+type Storage = HashMap<TypeId, Box<dyn Any>>;
+
+/// A context that only implements the required items,
+/// relying on the default implementations for layer access and checkpoints.
+struct StackContext {
+    layers: Vec<(TypeId, Storage)>,
+}
+
+impl Default for StackContext {
+    fn default() -> Self { Self { layers: vec![(TypeId::of::<()>(), HashMap::new())] } }
+}
+
+impl Context for StackContext {
+    type Scope = ();
+
+    fn nth_last_scope(&self, n: usize) -> Option<TypeId> {
+        let index = self.layers.len().checked_sub(n)?.checked_sub(1)?;
+        Some(self.layers[index].0)
+    }
+
+    fn get<T>(&self, scope: TypeId) -> Option<&T>
+    where
+        T: 'static,
+    {
+        let (_, map) = self.layers.iter().rev().find(|(type_id, _)| *type_id == scope)?;
+        map.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    fn get_each<T>(&self) -> Box<dyn Iterator<Item = &T> + '_>
+    where
+        T: 'static,
+    {
+        Box::new(
+            self.layers
+                .iter()
+                .rev()
+                .filter_map(|(_, map)| map.get(&TypeId::of::<T>())?.downcast_ref()),
+        )
+    }
+
+    fn get_mut<T, F>(&mut self, scope: TypeId, default: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+        T: 'static,
+    {
+        let (_, map) = self
+            .layers
+            .iter_mut()
+            .rev()
+            .find(|(type_id, _)| *type_id == scope)
+            .expect("Scope is not in the stack");
+        map.entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(default()))
+            .downcast_mut()
+            .expect("Storage type mismatch")
+    }
+
+    fn start_scope<T: 'static>(&mut self) { self.layers.push((TypeId::of::<T>(), HashMap::new())); }
+
+    fn end_scope(&mut self, (): ()) { self.layers.pop(); }
+}
+
+enum Schema {}
+
+impl xylem::Schema for Schema {
+    type Context = StackContext;
+    type Error = anyhow::Error;
+}
+
+impl SchemaExt for Schema {}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom)]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Zone>,
+    spawns: Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+    fn report_unused() -> bool { false }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom)]
+struct Spawn {
+    #[xylem(args(new = true))]
+    id:   Id<Schema, Spawn>,
+    zone: Id<Schema, Zone>,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+    fn report_unused() -> bool { false }
+}
+
+#[test]
+fn test_default_layer_access() {
+    let mut context = StackContext::default();
+
+    let zones = Vec::<Zone>::convert(
+        vec![
+            ZoneFrom { id: String::from("forest"), spawns: Vec::new() },
+            ZoneFrom {
+                id:     String::from("cave"),
+                spawns: vec![
+                    SpawnFrom { id: String::from("bat"), zone: String::from("forest") },
+                    SpawnFrom { id: String::from("spider"), zone: String::from("cave") },
+                ],
+            },
+        ],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    assert_eq!(zones[1].id.index(), 1);
+    assert_eq!(zones[1].spawns[1].id.index(), 1);
+    assert_eq!(zones[1].spawns[0].zone, zones[0].id);
+}
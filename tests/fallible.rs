@@ -0,0 +1,77 @@
+use std::any::TypeId;
+
+use xylem::{declare_schema, Context, DefaultContext, Id, Identifiable, NoArgs, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Xylem)]
+#[xylem(expose = BarFrom)]
+struct Bar {
+    #[xylem(args(new = true))]
+    id: Id<Schema, Bar>,
+}
+
+impl Identifiable<Schema> for Bar {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Bar> { self.id }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = QuxFrom)]
+struct Qux {
+    #[xylem(args(new = true))]
+    id: Id<Schema, Qux>,
+}
+
+impl Identifiable<Schema> for Qux {
+    type Scope = Bar;
+    fn id(&self) -> Id<Schema, Qux> { self.id }
+}
+
+#[test]
+fn test_declare_outside_scope() {
+    let mut context = DefaultContext::default();
+
+    let err =
+        Qux::convert(QuxFrom { id: String::from("qux") }, &mut context, &NoArgs).err().unwrap();
+    assert_eq!(err.to_string(), "id: Scope fallible::Bar is not an ancestor of fallible::Qux");
+}
+
+#[test]
+fn test_reference_outside_scope() {
+    let mut context = DefaultContext::default();
+
+    let err = Id::<Schema, Qux>::convert(String::from("qux"), &mut context, &Default::default())
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Scope fallible::Bar is not an ancestor of fallible::Qux");
+}
+
+#[test]
+fn test_declare_outside_identifiable() {
+    let mut context = DefaultContext::default();
+
+    let args = xylem::IdArgs { new: true, ..Default::default() };
+    let err = Id::<Schema, Bar>::convert(String::from("bar"), &mut context, &args).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "New fallible::Bar IDs can only be declared in the conversion of fallible::Bar"
+    );
+}
+
+#[test]
+fn test_checked_lookups() {
+    let mut context = DefaultContext::default();
+
+    assert_eq!(context.nth_last_scope(0), Some(TypeId::of::<()>()));
+    assert_eq!(context.nth_last_scope(1), None);
+    assert_eq!(context.nth_last_scope(usize::MAX), None);
+
+    assert!(context.try_get_mut::<u32, _>(TypeId::of::<Bar>(), Default::default).is_none());
+    assert!(context.try_get_mut::<u32, _>(TypeId::of::<()>(), Default::default).is_some());
+}
+
+#[test]
+fn test_id_overflow() {
+    assert!(Id::<Schema, Bar>::try_new(u32::MAX as usize).is_some());
+    assert!(Id::<Schema, Bar>::try_new(u32::MAX as usize + 1).is_none());
+}
@@ -0,0 +1,221 @@
+use std::any::TypeId;
+
+use xylem::id::{IdIndexing, IdRegistry};
+use xylem::{
+    declare_schema, Context, DefaultContext, Id, IdMap, Identifiable, NoArgs, SchemaExt, Xylem,
+    XylemInverse,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = WorldFrom, derive(Debug, Clone, PartialEq), inverse)]
+struct World {
+    zones: Vec<Zone>,
+    links: Vec<Link>,
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom, derive(Debug, Clone, PartialEq), inverse)]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Zone>,
+    spawns: Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+    fn report_unused() -> bool { false }
+    fn indexing() -> IdIndexing { IdIndexing::Hashed }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom, derive(Debug, Clone, PartialEq), inverse)]
+struct Spawn {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Spawn>,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+    fn report_unused() -> bool { false }
+    fn indexing() -> IdIndexing { IdIndexing::Hashed }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = LinkFrom, derive(Debug, Clone, PartialEq), inverse)]
+struct Link {
+    #[xylem(args(import = vec![TypeId::of::<Spawn>()]))]
+    zone:  Id<Schema, Zone>,
+    spawn: Id<Schema, Spawn>,
+}
+
+#[test]
+fn test_order_independent() {
+    let mut context = DefaultContext::default();
+    let world = World::convert(
+        WorldFrom {
+            zones: vec![
+                ZoneFrom {
+                    id:     String::from("forest"),
+                    spawns: vec![
+                        SpawnFrom { id: String::from("wolf") },
+                        SpawnFrom { id: String::from("bear") },
+                    ],
+                },
+                ZoneFrom {
+                    id:     String::from("cave"),
+                    spawns: vec![SpawnFrom { id: String::from("bat") }],
+                },
+            ],
+            links: vec![LinkFrom { zone: String::from("cave"), spawn: String::from("bat") }],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    let mut context = DefaultContext::default();
+    let moved = World::convert(
+        WorldFrom {
+            zones: vec![
+                ZoneFrom {
+                    id:     String::from("cave"),
+                    spawns: vec![SpawnFrom { id: String::from("bat") }],
+                },
+                ZoneFrom {
+                    id:     String::from("forest"),
+                    spawns: vec![
+                        SpawnFrom { id: String::from("bear") },
+                        SpawnFrom { id: String::from("wolf") },
+                    ],
+                },
+            ],
+            links: vec![LinkFrom { zone: String::from("cave"), spawn: String::from("bat") }],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    assert_eq!(world.zones[0].id, Id::hashed("forest"));
+    assert_eq!(moved.zones[1].id, world.zones[0].id);
+    assert_eq!(moved.zones[0].id, world.zones[1].id);
+    assert_eq!(moved.zones[1].spawns[0].id, world.zones[0].spawns[1].id);
+    assert_eq!(moved.links[0].spawn, Id::hashed("bat"));
+}
+
+#[test]
+fn test_round_trip() {
+    let from = WorldFrom {
+        zones: vec![
+            ZoneFrom {
+                id:     String::from("forest"),
+                spawns: vec![
+                    SpawnFrom { id: String::from("wolf") },
+                    SpawnFrom { id: String::from("bear") },
+                ],
+            },
+            ZoneFrom {
+                id:     String::from("cave"),
+                spawns: vec![SpawnFrom { id: String::from("bat") }],
+            },
+        ],
+        links: vec![LinkFrom { zone: String::from("cave"), spawn: String::from("bat") }],
+    };
+
+    let mut context = DefaultContext::default();
+    let world = World::convert(from.clone(), &mut context, &NoArgs).unwrap();
+    let unconverted = world.unconvert(&mut context, &NoArgs).unwrap();
+    assert_eq!(unconverted, from);
+}
+
+#[test]
+fn test_collision() {
+    let mut context = DefaultContext::default();
+    let err = World::convert(
+        WorldFrom {
+            zones: vec![
+                ZoneFrom { id: String::from("costarring"), spawns: Vec::new() },
+                ZoneFrom { id: String::from("liquid"), spawns: Vec::new() },
+            ],
+            links: Vec::new(),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "zones[1].id: Hash collision between IDs costarring and liquid");
+}
+
+#[test]
+fn test_registry() {
+    let mut context = DefaultContext::default();
+    let world = World::convert(
+        WorldFrom {
+            zones: vec![
+                ZoneFrom {
+                    id:     String::from("forest"),
+                    spawns: vec![
+                        SpawnFrom { id: String::from("wolf") },
+                        SpawnFrom { id: String::from("bear") },
+                    ],
+                },
+                ZoneFrom {
+                    id:     String::from("cave"),
+                    spawns: vec![SpawnFrom { id: String::from("bat") }],
+                },
+            ],
+            links: vec![LinkFrom { zone: String::from("cave"), spawn: String::from("bat") }],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    let registry = IdRegistry::<Schema, Zone>::from_context(&context);
+    assert_eq!(registry.name(world.zones[1].id).map(String::as_str), Some("cave"));
+    assert_eq!(registry.lookup("forest"), Some(world.zones[0].id));
+
+    let registry = IdRegistry::<Schema, Spawn>::from_context(&context);
+    let forest = [world.zones[0].id.index()];
+    assert_eq!(
+        registry.name_in(&forest, world.zones[0].spawns[1].id).map(String::as_str),
+        Some("bear")
+    );
+    assert_eq!(registry.lookup_in(&forest, "wolf"), Some(Id::hashed("wolf")));
+
+    let names = IdMap::from_registry_in(&registry, &forest, |_, name| name.to_string());
+    assert_eq!(names[Id::hashed("bear")], "bear");
+    assert_eq!(names.len(), 2);
+}
+
+#[test]
+fn test_rollback() {
+    let mut context = DefaultContext::default();
+    let result = context.transaction(|context| {
+        World::convert(
+            WorldFrom {
+                zones: vec![
+                    ZoneFrom { id: String::from("forest"), spawns: Vec::new() },
+                    ZoneFrom { id: String::from("cave"), spawns: Vec::new() },
+                ],
+                links: Vec::new(),
+            },
+            context,
+            &NoArgs,
+        )?;
+        Err::<(), _>(anyhow::anyhow!("rejected"))
+    });
+    assert!(result.is_err());
+
+    let from = WorldFrom {
+        zones: vec![ZoneFrom { id: String::from("cave"), spawns: Vec::new() }],
+        links: Vec::new(),
+    };
+    let world = World::convert(from.clone(), &mut context, &NoArgs).unwrap();
+    assert_eq!(world.zones[0].id, Id::hashed("cave"));
+    assert_eq!(world.unconvert(&mut context, &NoArgs).unwrap(), from);
+}
@@ -132,5 +132,5 @@ fn test_scoped_id() {
         &NoArgs,
     )
     .unwrap_err();
-    assert_eq!(second_err.to_string(), "Unknown ID alpha");
+    assert_eq!(second_err.to_string(), "bar[0].other: Unknown ID alpha");
 }
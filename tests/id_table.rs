@@ -0,0 +1,225 @@
+use xylem::id::IdTable;
+use xylem::{declare_schema, DefaultContext, Id, Identifiable, NoArgs, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom)]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:        Id<Schema, Zone>,
+    neighbour: Option<Id<Schema, Zone>>,
+    spawns:    Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom)]
+struct Spawn {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Spawn>,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+}
+
+fn convert(
+    table: &IdTable,
+    zones: Vec<ZoneFrom>,
+) -> (Result<Vec<Zone>, anyhow::Error>, DefaultContext) {
+    let mut context = DefaultContext::default();
+    table.seed::<Schema, Zone>(&mut context).unwrap();
+    table.seed::<Schema, Spawn>(&mut context).unwrap();
+    let zones = Vec::<Zone>::convert(zones, &mut context, &NoArgs);
+    (zones, context)
+}
+
+fn record(table: &mut IdTable, context: &DefaultContext) {
+    table.record::<Schema, Zone>(context);
+    table.record::<Schema, Spawn>(context);
+}
+
+#[test]
+fn test_stable_indices() {
+    let mut table = IdTable::default();
+    let (zones, context) = convert(
+        &table,
+        vec![
+            ZoneFrom {
+                id:        String::from("forest"),
+                neighbour: None,
+                spawns:    vec![
+                    SpawnFrom { id: String::from("wolf") },
+                    SpawnFrom { id: String::from("bear") },
+                ],
+            },
+            ZoneFrom {
+                id:        String::from("cave"),
+                neighbour: None,
+                spawns:    vec![SpawnFrom { id: String::from("bat") }],
+            },
+        ],
+    );
+    zones.unwrap();
+    record(&mut table, &context);
+
+    let (zones, context) = convert(
+        &table,
+        vec![
+            ZoneFrom { id: String::from("lake"), neighbour: None, spawns: Vec::new() },
+            ZoneFrom {
+                id:        String::from("cave"),
+                neighbour: Some(String::from("lake")),
+                spawns:    vec![SpawnFrom { id: String::from("bat") }],
+            },
+            ZoneFrom {
+                id:        String::from("forest"),
+                neighbour: None,
+                spawns:    vec![
+                    SpawnFrom { id: String::from("bear") },
+                    SpawnFrom { id: String::from("fox") },
+                ],
+            },
+        ],
+    );
+    let zones = zones.unwrap();
+
+    assert_eq!(zones[0].id.index(), 2);
+    assert_eq!(zones[1].neighbour, Some(zones[0].id));
+    assert_eq!(zones[1].id.index(), 1);
+    assert_eq!(zones[2].id.index(), 0);
+    assert_eq!(zones[2].spawns[0].id.index(), 1);
+    assert_eq!(zones[2].spawns[1].id.index(), 2);
+
+    record(&mut table, &context);
+    let zone_names: Vec<_> = table.names::<Schema, Zone>(&[]).collect();
+    assert_eq!(zone_names, [Some("forest"), Some("cave"), Some("lake")]);
+    let spawn_names: Vec<_> = table.names::<Schema, Spawn>(&[0]).collect();
+    assert_eq!(spawn_names, [None, Some("bear"), Some("fox")]);
+}
+
+#[test]
+fn test_tombstone() {
+    let mut table = IdTable::default();
+    let (zones, context) = convert(
+        &table,
+        vec![
+            ZoneFrom { id: String::from("forest"), neighbour: None, spawns: Vec::new() },
+            ZoneFrom { id: String::from("cave"), neighbour: None, spawns: Vec::new() },
+        ],
+    );
+    zones.unwrap();
+    record(&mut table, &context);
+
+    let (zones, context) = convert(
+        &table,
+        vec![ZoneFrom { id: String::from("cave"), neighbour: None, spawns: Vec::new() }],
+    );
+    assert_eq!(zones.unwrap()[0].id.index(), 1);
+
+    record(&mut table, &context);
+    let zone_names: Vec<_> = table.names::<Schema, Zone>(&[]).collect();
+    assert_eq!(zone_names, [None, Some("cave")]);
+
+    // the removed ID cannot be referenced, but its index is not reused
+    let (zones, _) = convert(
+        &table,
+        vec![ZoneFrom {
+            id:        String::from("lake"),
+            neighbour: Some(String::from("forest")),
+            spawns:    Vec::new(),
+        }],
+    );
+    assert_eq!(zones.unwrap_err().to_string(), "[0].neighbour: Unknown ID forest");
+
+    let (zones, _) = convert(
+        &table,
+        vec![ZoneFrom { id: String::from("lake"), neighbour: None, spawns: Vec::new() }],
+    );
+    assert_eq!(zones.unwrap()[0].id.index(), 2);
+
+    // a removed ID regains its index when it is declared again
+    let (zones, _) = convert(
+        &table,
+        vec![ZoneFrom {
+            id:        String::from("forest"),
+            neighbour: None,
+            spawns:    Vec::new(),
+        }],
+    );
+    assert_eq!(zones.unwrap()[0].id.index(), 0);
+}
+
+#[test]
+fn test_empty_name() {
+    let mut table = IdTable::default();
+    let (zones, context) = convert(
+        &table,
+        vec![
+            ZoneFrom { id: String::from(""), neighbour: None, spawns: Vec::new() },
+            ZoneFrom {
+                id:        String::from("cave"),
+                neighbour: Some(String::from("")),
+                spawns:    Vec::new(),
+            },
+        ],
+    );
+    let zones = zones.unwrap();
+    assert_eq!(zones[1].neighbour, Some(zones[0].id));
+    record(&mut table, &context);
+
+    let (zones, _) = convert(
+        &table,
+        vec![
+            ZoneFrom { id: String::from("lake"), neighbour: None, spawns: Vec::new() },
+            ZoneFrom { id: String::from(""), neighbour: None, spawns: Vec::new() },
+        ],
+    );
+    let zones = zones.unwrap();
+    assert_eq!(zones[0].id.index(), 2);
+    assert_eq!(zones[1].id.index(), 0);
+
+    let (zones, _) = convert(
+        &table,
+        vec![
+            ZoneFrom { id: String::from(""), neighbour: None, spawns: Vec::new() },
+            ZoneFrom { id: String::from(""), neighbour: None, spawns: Vec::new() },
+        ],
+    );
+    assert_eq!(zones.unwrap_err().to_string(), "[1].id: Duplicate ID ");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let mut table = IdTable::default();
+    let (zones, context) = convert(
+        &table,
+        vec![
+            ZoneFrom {
+                id:        String::from("forest"),
+                neighbour: None,
+                spawns:    vec![SpawnFrom { id: String::from("wolf") }],
+            },
+            ZoneFrom { id: String::from("cave"), neighbour: None, spawns: Vec::new() },
+        ],
+    );
+    zones.unwrap();
+    record(&mut table, &context);
+
+    let json = serde_json::to_string(&table).unwrap();
+    let loaded: IdTable = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, table);
+
+    let (zones, _) = convert(
+        &loaded,
+        vec![ZoneFrom { id: String::from("cave"), neighbour: None, spawns: Vec::new() }],
+    );
+    assert_eq!(zones.unwrap()[0].id.index(), 1);
+}
@@ -0,0 +1,99 @@
+use xylem::id::{IdRegistry, IdTable};
+use xylem::{
+    declare_schema, DefaultContext, Id, IdMap, IdVec, Identifiable, NoArgs, SchemaExt, Xylem,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ItemFrom)]
+struct Item {
+    #[xylem(args(new = true))]
+    id: Id<Schema, Item>,
+}
+
+impl Identifiable<Schema> for Item {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Item> { self.id }
+}
+
+#[test]
+fn test_id_vec() {
+    let mut prices = IdVec::<Schema, Item, u32>::new();
+    let sword = prices.push(10);
+    let bow = prices.push(7);
+    assert_eq!(bow.index(), 1);
+
+    prices[sword] += 5;
+    assert_eq!(prices[sword], 15);
+    assert_eq!(prices.get(Id::new(2)), None);
+
+    let entries: Vec<_> = prices.iter().map(|(id, &price)| (id, price)).collect();
+    assert_eq!(entries, [(sword, 15), (bow, 7)]);
+    let ids: Vec<_> = prices.ids().collect();
+    assert_eq!(ids, [sword, bow]);
+}
+
+#[test]
+fn test_id_map() {
+    let mut stock = IdMap::<Schema, Item, u32>::new();
+    assert_eq!(stock.insert(Id::new(3), 1), None);
+    assert_eq!(stock.insert(Id::new(1), 2), None);
+    assert_eq!(stock.insert(Id::new(3), 4), Some(1));
+
+    stock[Id::new(1)] += 1;
+    assert_eq!(stock[Id::new(1)], 3);
+    assert!(!stock.contains(Id::new(0)));
+
+    let entries: Vec<_> = stock.iter().map(|(id, &count)| (id.index(), count)).collect();
+    assert_eq!(entries, [(1, 3), (3, 4)]);
+}
+
+#[test]
+fn test_from_registry() {
+    let mut table = IdTable::default();
+    let mut context = DefaultContext::default();
+    Vec::<Item>::convert(
+        vec![ItemFrom { id: String::from("sword") }, ItemFrom { id: String::from("bow") }],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    table.record::<Schema, Item>(&context);
+
+    let mut context = DefaultContext::default();
+    table.seed::<Schema, Item>(&mut context).unwrap();
+    let items = Vec::<Item>::convert(
+        vec![ItemFrom { id: String::from("bow") }, ItemFrom { id: String::from("arrow") }],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    let registry = IdRegistry::<Schema, Item>::from_context(&context);
+
+    // the reserved index of sword is kept in the dense list
+    let names = IdVec::from_registry(&registry, |_, name| name.cloned());
+    assert_eq!(names.len(), 3);
+    assert_eq!(names[items[0].id].as_deref(), Some("bow"));
+    assert_eq!(names[items[1].id].as_deref(), Some("arrow"));
+    assert_eq!(names[Id::new(0)], None);
+
+    let lengths = IdMap::from_registry(&registry, |_, name| name.len());
+    let entries: Vec<_> = lengths.into_iter().map(|(id, len)| (id.index(), len)).collect();
+    assert_eq!(entries, [(1, 3), (2, 5)]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let prices: IdVec<Schema, Item, u32> = vec![10, 7].into();
+    let json = serde_json::to_string(&prices).unwrap();
+    assert_eq!(json, "[10,7]");
+    let loaded: IdVec<Schema, Item, u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, prices);
+
+    let stock: IdMap<Schema, Item, u32> = [(Id::new(3), 2)].into_iter().collect();
+    let json = serde_json::to_string(&stock).unwrap();
+    let loaded: IdMap<Schema, Item, u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, stock);
+}
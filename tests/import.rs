@@ -0,0 +1,117 @@
+use std::any::TypeId;
+
+use xylem::{declare_schema, DefaultContext, Id, Identifiable, NoArgs, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Xylem)]
+#[xylem(expose = AddressFrom)]
+struct Address {
+    #[xylem(args(import = vec![TypeId::of::<City>()]))]
+    region:   Id<Schema, Region>,
+    #[xylem(args(import = vec![TypeId::of::<Building>()]))]
+    city:     Id<Schema, City>,
+    building: Id<Schema, Building>,
+}
+
+#[derive(Xylem)]
+#[xylem(expose = RegionFrom)]
+struct Region {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Region>,
+    cities: Vec<City>,
+}
+
+impl Identifiable<Schema> for Region {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Region> { self.id }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = CityFrom)]
+struct City {
+    #[xylem(args(new = true, track = true))]
+    id:        Id<Schema, City>,
+    buildings: Vec<Building>,
+}
+
+impl Identifiable<Schema> for City {
+    type Scope = Region;
+    fn id(&self) -> Id<Schema, City> { self.id }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = BuildingFrom)]
+struct Building {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Building>,
+}
+
+impl Identifiable<Schema> for Building {
+    type Scope = City;
+    fn id(&self) -> Id<Schema, Building> { self.id }
+}
+
+#[test]
+fn test_transitive_import() {
+    let mut context = DefaultContext::default();
+    let regions = Vec::<Region>::convert(
+        vec![
+            RegionFrom {
+                id:     String::from("north"),
+                cities: vec![CityFrom {
+                    id:        String::from("port"),
+                    buildings: vec![BuildingFrom { id: String::from("tower") }],
+                }],
+            },
+            RegionFrom {
+                id:     String::from("south"),
+                cities: vec![
+                    CityFrom {
+                        id:        String::from("oasis"),
+                        buildings: vec![BuildingFrom { id: String::from("well") }],
+                    },
+                    CityFrom {
+                        id:        String::from("port"),
+                        buildings: vec![
+                            BuildingFrom { id: String::from("dock") },
+                            BuildingFrom { id: String::from("tower") },
+                        ],
+                    },
+                ],
+            },
+        ],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(regions[1].cities[1].buildings[1].id.index(), 1);
+
+    let from = AddressFrom {
+        region:   String::from("south"),
+        city:     String::from("port"),
+        building: String::from("tower"),
+    };
+    let address = Address::convert(from, &mut context, &NoArgs).unwrap();
+    assert_eq!(address.region.index(), 1);
+    assert_eq!(address.city.index(), 1);
+    assert_eq!(address.building.index(), 1);
+
+    let from = AddressFrom {
+        region:   String::from("north"),
+        city:     String::from("port"),
+        building: String::from("tower"),
+    };
+    let address = Address::convert(from, &mut context, &NoArgs).unwrap();
+    assert_eq!(address.region.index(), 0);
+    assert_eq!(address.city.index(), 0);
+    assert_eq!(address.building.index(), 0);
+
+    let from = AddressFrom {
+        region:   String::from("north"),
+        city:     String::from("port"),
+        building: String::from("dock"),
+    };
+    let err = Address::convert(from, &mut context, &NoArgs).err().unwrap();
+    assert_eq!(err.to_string(), "building: Unknown ID dock");
+}
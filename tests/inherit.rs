@@ -0,0 +1,148 @@
+use xylem::id::IdArgs;
+use xylem::{
+    declare_schema, finalize, inherit, DefaultContext, Id, Identifiable, LazyId, NoArgs, SchemaExt,
+    Xylem, XylemInverse,
+};
+
+declare_schema!(Base: SchemaExt);
+declare_schema!(Child: extends Base, SchemaExt);
+
+#[derive(Debug, PartialEq)]
+struct Colour(String);
+
+impl Xylem<Base> for Colour {
+    type From = String;
+    type Args = NoArgs;
+
+    fn convert_impl(
+        from: Self::From,
+        _context: &mut DefaultContext,
+        _args: &Self::Args,
+    ) -> Result<Self, anyhow::Error> {
+        match from.as_str() {
+            "red" => Ok(Colour(String::from("#ff0000"))),
+            "blue" => Ok(Colour(String::from("#0000ff"))),
+            _ => Err(anyhow::anyhow!("Unknown colour {}", from)),
+        }
+    }
+}
+
+/// The child schema also accepts hex codes.
+impl Xylem<Child> for Colour {
+    type From = String;
+    type Args = NoArgs;
+
+    fn convert_impl(
+        from: Self::From,
+        context: &mut DefaultContext,
+        args: &Self::Args,
+    ) -> Result<Self, anyhow::Error> {
+        if from.starts_with('#') {
+            Ok(Colour(from))
+        } else {
+            <Colour as Xylem<Base>>::convert_impl(from, context, args)
+        }
+    }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(schema = Base, expose = ItemFrom)]
+struct Item {
+    #[xylem(args(new = true))]
+    id:     Id<Base, Item>,
+    colour: Colour,
+    next:   Option<LazyId<Base, Item>>,
+}
+
+impl Identifiable<Base> for Item {
+    type Scope = ();
+    fn id(&self) -> Id<Base, Item> { self.id }
+}
+
+inherit!(Child: Item);
+inherit!(impl<X: Identifiable<Base>> Child: Id<Base, X>; inverse);
+
+#[derive(Debug, Xylem)]
+#[xylem(schema = Child, expose = ShopFrom)]
+struct Shop {
+    items:     Vec<Item>,
+    favourite: Id<Base, Item>,
+    colour:    Colour,
+}
+
+#[test]
+fn test_override() {
+    let mut context = DefaultContext::default();
+
+    let shop = Shop::convert(
+        ShopFrom {
+            items:     vec![ItemFrom {
+                id:     String::from("apple"),
+                colour: String::from("red"),
+                next:   None,
+            }],
+            favourite: String::from("apple"),
+            colour:    String::from("#00ff00"),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(shop.colour, Colour(String::from("#00ff00")));
+    assert_eq!(shop.items[0].colour, Colour(String::from("#ff0000")));
+
+    let mut context = DefaultContext::default();
+
+    let err = Shop::convert(
+        ShopFrom {
+            items:     vec![ItemFrom {
+                id:     String::from("apple"),
+                colour: String::from("#00ff00"),
+                next:   None,
+            }],
+            favourite: String::from("apple"),
+            colour:    String::from("blue"),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "items[0].colour: Unknown colour #00ff00");
+}
+
+#[test]
+fn test_shared_state() {
+    let mut context = DefaultContext::default();
+
+    let shop = Shop::convert(
+        ShopFrom {
+            items:     vec![
+                ItemFrom {
+                    id:     String::from("apple"),
+                    colour: String::from("red"),
+                    next:   Some(String::from("berry")),
+                },
+                ItemFrom {
+                    id:     String::from("berry"),
+                    colour: String::from("blue"),
+                    next:   None,
+                },
+            ],
+            favourite: String::from("berry"),
+            colour:    String::from("blue"),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(shop.favourite, shop.items[1].id);
+
+    // finalizers registered by the parent schema are run by the child schema
+    assert_eq!(shop.items[0].next.as_ref().unwrap().get(), None);
+    finalize::<Child>(&mut context).unwrap();
+    assert_eq!(shop.items[0].next.as_ref().unwrap().get(), Some(shop.items[1].id));
+
+    let name =
+        XylemInverse::<Child>::unconvert(shop.favourite, &mut context, &IdArgs::default()).unwrap();
+    assert_eq!(name, "berry");
+}
@@ -0,0 +1,128 @@
+use std::any::TypeId;
+
+use xylem::{
+    declare_schema, DefaultContext, Id, IdString, Identifiable, NoArgs, SchemaExt, Xylem,
+    XylemInverse,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = WorldFrom, derive(Debug, PartialEq), inverse)]
+struct World {
+    zones: Vec<Zone>,
+    links: Vec<Link>,
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom, derive(Debug, PartialEq), inverse)]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:        Id<Schema, Zone>,
+    id_string: IdString<Schema, Zone>,
+    spawns:    Vec<Spawn>,
+    neighbour: Option<Id<Schema, Zone>>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom, derive(Debug, PartialEq), inverse)]
+struct Spawn {
+    #[xylem(args(new = true, track = true))]
+    id:      Id<Schema, Spawn>,
+    #[xylem(transform = parse_weight(String), inverse = format_weight)]
+    weight:  u32,
+    #[xylem(default = true)]
+    #[allow(dead_code)]
+    enabled: bool,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+}
+
+fn parse_weight(weight: String) -> anyhow::Result<u32> { Ok(weight.parse()?) }
+
+fn format_weight(weight: u32) -> anyhow::Result<String> { Ok(weight.to_string()) }
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = LinkFrom, derive(Debug, PartialEq), inverse)]
+struct Link {
+    #[xylem(args(import = vec![TypeId::of::<Spawn>()]))]
+    zone:  Id<Schema, Zone>,
+    spawn: Id<Schema, Spawn>,
+}
+
+fn world_from() -> WorldFrom {
+    WorldFrom {
+        zones: vec![
+            ZoneFrom {
+                id:        String::from("forest"),
+                id_string: (),
+                spawns:    vec![
+                    SpawnFrom { id: String::from("wolf"), weight: String::from("3") },
+                    SpawnFrom { id: String::from("bear"), weight: String::from("1") },
+                ],
+                neighbour: None,
+            },
+            ZoneFrom {
+                id:        String::from("cave"),
+                id_string: (),
+                spawns:    vec![SpawnFrom {
+                    id:     String::from("bat"),
+                    weight: String::from("5"),
+                }],
+                neighbour: Some(String::from("forest")),
+            },
+        ],
+        links: vec![
+            LinkFrom { zone: String::from("cave"), spawn: String::from("bat") },
+            LinkFrom { zone: String::from("forest"), spawn: String::from("bear") },
+        ],
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let mut context = DefaultContext::default();
+
+    let world = World::convert(world_from(), &mut context, &NoArgs).unwrap();
+    assert_eq!(world.links[1].spawn.index(), 1);
+
+    let from = world.unconvert(&mut context, &NoArgs).unwrap();
+    assert_eq!(from, world_from());
+}
+
+#[test]
+fn test_edit_round_trip() {
+    let mut context = DefaultContext::default();
+
+    let mut world = World::convert(world_from(), &mut context, &NoArgs).unwrap();
+    world.zones[1].neighbour = None;
+    world.zones[0].neighbour = Some(world.zones[1].id);
+    world.links.remove(0);
+
+    let from = world.unconvert(&mut context, &NoArgs).unwrap();
+    assert_eq!(from.zones[0].neighbour.as_deref(), Some("cave"));
+    assert_eq!(from.zones[1].neighbour, None);
+    assert_eq!(
+        from.links,
+        vec![LinkFrom { zone: String::from("forest"), spawn: String::from("bear") }]
+    );
+}
+
+#[test]
+fn test_unknown_id() {
+    let mut context = DefaultContext::default();
+
+    let mut world = World::convert(world_from(), &mut context, &NoArgs).unwrap();
+    world.zones[0].neighbour = Some(Id::new(5));
+
+    let err = world.unconvert(&mut context, &NoArgs).unwrap_err();
+    assert_eq!(err.to_string(), "zones[0].neighbour: Unknown inverse::Zone ID 5");
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use xylem::id::{IdRegistry, IdTable, KeyedIdentifiable};
+use xylem::{
+    declare_schema, finalize, Context, DefaultContext, Id, IdAliases, IdString, LazyId, NoArgs,
+    SchemaExt, Xylem, XylemInverse,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ShopFrom, derive(Debug, Clone, PartialEq), inverse)]
+struct Shop {
+    items:  Vec<Item>,
+    offers: Vec<Offer>,
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ItemFrom, derive(Debug, Clone, PartialEq), inverse)]
+struct Item {
+    #[xylem(args(new = true))]
+    id:      Id<Schema, Item>,
+    key:     IdString<Schema, Item>,
+    aliases: IdAliases<Schema, Item>,
+    upgrade: Option<LazyId<Schema, Item>>,
+}
+
+impl KeyedIdentifiable<Schema> for Item {
+    type Key = u64;
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Item> { self.id }
+    fn report_unused() -> bool { false }
+    fn parse_key(name: &str) -> Option<u64> { name.parse().ok() }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = OfferFrom, derive(Debug, Clone, PartialEq), inverse)]
+struct Offer {
+    item: Id<Schema, Item>,
+}
+
+#[test]
+fn test_integer_keys() {
+    let from = ShopFrom {
+        items:  vec![
+            ItemFrom { id: 1024, key: (), aliases: vec![1], upgrade: Some(2048) },
+            ItemFrom { id: 2048, key: (), aliases: Vec::new(), upgrade: None },
+        ],
+        offers: vec![OfferFrom { item: 2048 }, OfferFrom { item: 1 }],
+    };
+    let mut context = DefaultContext::default();
+    let shop = Shop::convert(from.clone(), &mut context, &NoArgs).unwrap();
+    finalize::<Schema>(&mut context).unwrap();
+
+    assert_eq!(*shop.items[1].key.value(), 2048);
+    assert_eq!(shop.items[0].upgrade.as_ref().unwrap().id(), shop.items[1].id);
+    assert_eq!(shop.offers[0].item, shop.items[1].id);
+    assert_eq!(shop.offers[1].item, shop.items[0].id);
+    assert_eq!(context.take_warnings()[0].message(), "ID 1 is a deprecated alias of 1024");
+
+    let registry = IdRegistry::<Schema, Item>::from_context(&context);
+    assert_eq!(registry.lookup(&2048), Some(shop.items[1].id));
+    assert_eq!(registry.name(shop.items[0].id), Some(&1024));
+
+    let unconverted = shop.unconvert(&mut context, &NoArgs).unwrap();
+    assert_eq!(unconverted.items, from.items);
+    assert_eq!(unconverted.offers[0], OfferFrom { item: 2048 });
+}
+
+#[test]
+fn test_unknown_key() {
+    let mut context = DefaultContext::default();
+    let err = Shop::convert(
+        ShopFrom {
+            items:  vec![ItemFrom {
+                id:      1024,
+                key:     (),
+                aliases: Vec::new(),
+                upgrade: None,
+            }],
+            offers: vec![OfferFrom { item: 7 }],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "offers[0].item: Unknown ID 7");
+}
+
+#[test]
+fn test_seed_keys() {
+    let mut context = DefaultContext::default();
+    Shop::convert(
+        ShopFrom {
+            items:  vec![
+                ItemFrom { id: 1024, key: (), aliases: vec![1], upgrade: Some(2048) },
+                ItemFrom { id: 2048, key: (), aliases: Vec::new(), upgrade: None },
+            ],
+            offers: vec![OfferFrom { item: 2048 }, OfferFrom { item: 1 }],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    let mut table = IdTable::default();
+    table.record::<Schema, Item>(&context);
+
+    let mut context = DefaultContext::default();
+    table.seed::<Schema, Item>(&mut context).unwrap();
+    let shop = Shop::convert(
+        ShopFrom {
+            items:  vec![ItemFrom {
+                id:      2048,
+                key:     (),
+                aliases: Vec::new(),
+                upgrade: None,
+            }],
+            offers: vec![],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(shop.items[0].id.index(), 1);
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SlotFrom)]
+struct Slot {
+    #[xylem(args(new = true))]
+    id: Id<Schema, Slot>,
+}
+
+/// A key whose display form omits the container.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum SlotKey {
+    Bag(u32),
+    Chest(u32),
+}
+
+impl fmt::Display for SlotKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bag(index) | Self::Chest(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+impl KeyedIdentifiable<Schema> for Slot {
+    type Key = SlotKey;
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Slot> { self.id }
+    fn report_unused() -> bool { false }
+    fn parse_key(_: &str) -> Option<SlotKey> { None }
+}
+
+#[test]
+fn test_same_display() {
+    let mut context = DefaultContext::default();
+    let slots = Vec::<Slot>::convert(
+        vec![SlotFrom { id: SlotKey::Bag(3) }, SlotFrom { id: SlotKey::Chest(3) }],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    let id = Id::<Schema, Slot>::convert(SlotKey::Chest(3), &mut context, &Default::default());
+    assert_eq!(id.unwrap(), slots[1].id);
+    let registry = IdRegistry::<Schema, Slot>::from_context(&context);
+    assert_eq!(registry.name(slots[0].id), Some(&SlotKey::Bag(3)));
+}
+
+#[test]
+fn test_seed_unparsable() {
+    let mut context = DefaultContext::default();
+    Vec::<Slot>::convert(vec![SlotFrom { id: SlotKey::Bag(3) }], &mut context, &NoArgs).unwrap();
+    let mut table = IdTable::default();
+    table.record::<Schema, Slot>(&context);
+
+    let mut context = DefaultContext::default();
+    let err = table.seed::<Schema, Slot>(&mut context).unwrap_err();
+    assert!(err.to_string().contains("Cannot parse saved"), "{}", err);
+}
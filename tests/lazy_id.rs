@@ -0,0 +1,119 @@
+use xylem::{
+    declare_schema, finalize, DefaultContext, Id, Identifiable, LazyId, NoArgs, SchemaExt, Xylem,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom)]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Zone>,
+    next:   Option<LazyId<Schema, Zone>>,
+    spawns: Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom)]
+struct Spawn {
+    #[xylem(args(new = true))]
+    id:   Id<Schema, Spawn>,
+    next: Option<LazyId<Schema, Spawn>>,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+}
+
+#[test]
+fn test_scoped_forward_reference() {
+    let mut context = DefaultContext::default();
+
+    let zone = Zone::convert(
+        ZoneFrom {
+            id:     String::from("forest"),
+            next:   None,
+            spawns: vec![
+                SpawnFrom { id: String::from("wolf"), next: Some(String::from("bear")) },
+                SpawnFrom { id: String::from("bear"), next: Some(String::from("wolf")) },
+            ],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    assert_eq!(zone.spawns[0].next.as_ref().unwrap().id(), zone.spawns[1].id);
+    assert_eq!(zone.spawns[1].next.as_ref().unwrap().id(), zone.spawns[0].id);
+}
+
+#[test]
+fn test_global_forward_reference() {
+    let mut context = DefaultContext::default();
+
+    let forest = Zone::convert(
+        ZoneFrom {
+            id:     String::from("forest"),
+            next:   Some(String::from("cave")),
+            spawns: Vec::new(),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(forest.next.as_ref().unwrap().get(), None);
+
+    let cave = Zone::convert(
+        ZoneFrom {
+            id:     String::from("cave"),
+            next:   Some(String::from("forest")),
+            spawns: Vec::new(),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(cave.next.as_ref().unwrap().get(), Some(forest.id));
+
+    finalize::<Schema>(&mut context).unwrap();
+    assert_eq!(forest.next.as_ref().unwrap().get(), Some(cave.id));
+}
+
+#[test]
+fn test_unknown_forward_reference() {
+    let mut context = DefaultContext::default();
+
+    let err = Zone::convert(
+        ZoneFrom {
+            id:     String::from("forest"),
+            next:   Some(String::from("nowhere")),
+            spawns: vec![
+                SpawnFrom { id: String::from("wolf"), next: Some(String::from("fox")) },
+                SpawnFrom { id: String::from("bear"), next: Some(String::from("wolf")) },
+            ],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "spawns[0].next: Unknown ID fox");
+
+    Zone::convert(
+        ZoneFrom {
+            id:     String::from("cave"),
+            next:   Some(String::from("nowhere")),
+            spawns: Vec::new(),
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    let err = finalize::<Schema>(&mut context).unwrap_err();
+    assert_eq!(err.to_string(), "next: Unknown ID nowhere");
+}
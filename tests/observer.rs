@@ -0,0 +1,184 @@
+use std::any::type_name;
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+use xylem::{
+    declare_schema, DefaultContext, NoArgs, SchemaExt, ScopeEnd, ScopeObserver, ScopeOutcome,
+    ScopeStart, Xylem,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[allow(dead_code)] // it's only used to observe the conversions.
+struct Number(u32);
+
+impl Xylem<Schema> for Number {
+    type From = String;
+    type Args = NoArgs;
+
+    fn convert_impl(
+        from: Self::From,
+        _context: &mut DefaultContext,
+        _args: &Self::Args,
+    ) -> Result<Self, anyhow::Error> {
+        if from == "panic" {
+            panic!("number panicked");
+        }
+        Ok(Number(from.parse()?))
+    }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = FooFrom)]
+struct Foo {
+    #[allow(dead_code)] // it's only used to observe the conversions.
+    numbers: Vec<Number>,
+}
+
+type Event = (usize, &'static str, Option<ScopeOutcome>);
+
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Rc<RefCell<Vec<Event>>>,
+}
+
+impl ScopeObserver for Recorder {
+    fn start_scope(&mut self, event: &ScopeStart) {
+        self.events.borrow_mut().push((event.depth(), event.type_name(), None));
+    }
+
+    fn end_scope(&mut self, event: &ScopeEnd) {
+        self.events.borrow_mut().push((event.depth(), event.type_name(), Some(event.outcome())));
+    }
+}
+
+fn convert(numbers: &[&str]) -> Vec<Event> {
+    let recorder = Recorder::default();
+    let mut context = DefaultContext::default();
+    context.add_observer(recorder.clone());
+
+    let from = FooFrom { numbers: numbers.iter().map(|number| number.to_string()).collect() };
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| Foo::convert(from, &mut context, &NoArgs)));
+
+    let events = recorder.events.borrow().clone();
+    events
+}
+
+fn expect_single(outcome: ScopeOutcome) -> Vec<Event> {
+    vec![
+        (0, type_name::<Foo>(), None),
+        (1, type_name::<Vec<Number>>(), None),
+        (2, type_name::<Number>(), None),
+        (2, type_name::<Number>(), Some(outcome)),
+        (1, type_name::<Vec<Number>>(), Some(outcome)),
+        (0, type_name::<Foo>(), Some(outcome)),
+    ]
+}
+
+#[test]
+fn test_success() {
+    assert_eq!(convert(&["1"]), expect_single(ScopeOutcome::Success));
+}
+
+#[test]
+fn test_failure() {
+    assert_eq!(convert(&["x"]), expect_single(ScopeOutcome::Failure));
+}
+
+#[test]
+fn test_panic() {
+    assert_eq!(convert(&["panic"]), expect_single(ScopeOutcome::Panic));
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_observer {
+    use std::any::type_name;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+    use xylem::{DefaultContext, NoArgs, TracingObserver, Xylem};
+
+    use super::{Foo, FooFrom, Number};
+
+    /// Records the spans and events as strings.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        spans:  Arc<Mutex<Vec<String>>>,
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    /// Collects the fields of a span or event as `name=value` strings.
+    #[derive(Default)]
+    struct Fields(Vec<String>);
+
+    impl Visit for Fields {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push(format!("{}={}", field.name(), value));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() != "duration" {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata) -> bool { true }
+
+        fn new_span(&self, span: &Attributes) -> Id {
+            let mut fields = Fields::default();
+            span.record(&mut fields);
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(fields.0.join(" "));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event) {
+            let mut fields = Fields::default();
+            event.record(&mut fields);
+            self.events.lock().unwrap().push(fields.0.join(" "));
+        }
+
+        fn enter(&self, span: &Id) {
+            let spans = self.spans.lock().unwrap();
+            let span = spans[span.into_u64() as usize - 1].clone();
+            self.events.lock().unwrap().push(format!("enter {}", span));
+        }
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn test_tracing_spans() {
+        let recorder = Recorder::default();
+        let mut context = DefaultContext::default();
+        context.add_observer(TracingObserver::default());
+
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let from = FooFrom { numbers: vec![String::from("x")] };
+            assert!(Foo::convert(from, &mut context, &NoArgs).is_err());
+        });
+
+        let events = recorder.events.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            vec![
+                format!("enter ty={} depth=0", type_name::<Foo>()),
+                format!("enter ty={} depth=1", type_name::<Vec<Number>>()),
+                format!("enter ty={} depth=2", type_name::<Number>()),
+                String::from("outcome=Failure"),
+                String::from("outcome=Failure"),
+                String::from("outcome=Failure"),
+            ]
+        );
+    }
+}
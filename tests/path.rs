@@ -0,0 +1,113 @@
+#![allow(dead_code)] // the types are only converted to test the errors.
+
+use std::collections::BTreeMap;
+
+use xylem::{declare_schema, DefaultContext, FieldPath, NoArgs, PathError, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+impl Xylem<Schema> for u32 {
+    type From = String;
+    type Args = NoArgs;
+
+    fn convert_impl(
+        from: Self::From,
+        _context: &mut DefaultContext,
+        _args: &Self::Args,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(from.parse()?)
+    }
+}
+
+impl Xylem<Schema> for String {
+    type From = Self;
+    type Args = NoArgs;
+
+    fn convert_impl(
+        from: Self::From,
+        _context: &mut DefaultContext,
+        _args: &Self::Args,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(from)
+    }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = WorldFrom)]
+struct World {
+    zones: BTreeMap<String, Zone>,
+}
+
+#[derive(Xylem)]
+#[xylem(expose = ZoneFrom)]
+enum Zone {
+    Forest { spawns: Vec<Spawn> },
+    Cave(u32),
+}
+
+#[derive(Xylem)]
+#[xylem(expose = SpawnFrom)]
+struct Spawn(u32, Option<u32>);
+
+#[derive(Xylem)]
+#[xylem(expose = LootFrom)]
+struct Loot {
+    weights: BTreeMap<u32, u32>,
+}
+
+fn convert_err(from: WorldFrom) -> anyhow::Error {
+    let mut context = DefaultContext::default();
+    World::convert(from, &mut context, &NoArgs).err().expect("Conversion should fail")
+}
+
+#[test]
+fn test_nested_path() {
+    let err = convert_err(WorldFrom {
+        zones: [(
+            String::from("forest"),
+            ZoneFrom::Forest {
+                spawns: vec![
+                    SpawnFrom(String::from("1"), None),
+                    SpawnFrom(String::from("2"), Some(String::from("x"))),
+                ],
+            },
+        )]
+        .into_iter()
+        .collect(),
+    });
+
+    assert_eq!(err.to_string(), "zones[0]::Forest.spawns[1].1: invalid digit found in string");
+
+    let path_err = err.downcast_ref::<PathError>().unwrap();
+    assert_eq!(path_err.path().segments().len(), 5);
+    assert_eq!(path_err.error().to_string(), "invalid digit found in string");
+}
+
+#[test]
+fn test_variant_path() {
+    let err = convert_err(WorldFrom {
+        zones: [(String::from("cave"), ZoneFrom::Cave(String::new()))].into_iter().collect(),
+    });
+
+    assert_eq!(err.to_string(), "zones[0]::Cave.0: cannot parse integer from empty string");
+}
+
+#[test]
+fn test_empty_path() {
+    assert_eq!(FieldPath::default().to_string(), "");
+}
+
+#[test]
+fn test_map_key_path() {
+    let convert = |weights: &[(&str, &str)]| {
+        let weights =
+            weights.iter().map(|&(key, value)| (String::from(key), String::from(value))).collect();
+        let mut context = DefaultContext::default();
+        Loot::convert(LootFrom { weights }, &mut context, &NoArgs).err().unwrap()
+    };
+
+    let err = convert(&[("1", "2"), ("x", "3")]);
+    assert_eq!(err.to_string(), "weights[key 1]: invalid digit found in string");
+    let err = convert(&[("1", "2"), ("3", "x")]);
+    assert_eq!(err.to_string(), "weights[1]: invalid digit found in string");
+}
@@ -0,0 +1,142 @@
+use xylem::{declare_schema, DefaultContext, Id, Identifiable, NoArgs, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Xylem)]
+#[xylem(expose = FooFrom)]
+struct Foo {
+    #[xylem(args(separator = Some("/")))]
+    qux:  Id<Schema, Qux>,
+    #[xylem(args(separator = Some("::")))]
+    quux: Option<Id<Schema, Quux>>,
+}
+
+#[derive(Xylem)]
+#[xylem(expose = BarFrom)]
+struct Bar {
+    #[xylem(args(new = true))]
+    id:  Id<Schema, Bar>,
+    #[allow(dead_code)] // it's only used to declare the IDs.
+    qux: Vec<Qux>,
+}
+
+impl Identifiable<Schema> for Bar {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Bar> { self.id }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = QuxFrom)]
+struct Qux {
+    #[xylem(args(new = true, track = true))]
+    id:   Id<Schema, Qux>,
+    #[allow(dead_code)] // it's only used to declare the IDs.
+    quux: Vec<Quux>,
+}
+
+impl Identifiable<Schema> for Qux {
+    type Scope = Bar;
+    fn id(&self) -> Id<Schema, Qux> { self.id }
+}
+
+#[derive(Xylem)]
+#[xylem(expose = QuuxFrom)]
+struct Quux {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Quux>,
+}
+
+impl Identifiable<Schema> for Quux {
+    type Scope = Qux;
+    fn id(&self) -> Id<Schema, Quux> { self.id }
+}
+
+fn setup() -> DefaultContext {
+    let mut context = DefaultContext::default();
+    Vec::<Bar>::convert(
+        vec![
+            BarFrom {
+                id:  String::from("one"),
+                qux: vec![QuxFrom {
+                    id:   String::from("two"),
+                    quux: vec![QuuxFrom { id: String::from("x") }],
+                }],
+            },
+            BarFrom {
+                id:  String::from("four"),
+                qux: vec![
+                    QuxFrom { id: String::from("five"), quux: Vec::new() },
+                    QuxFrom {
+                        id:   String::from("two"),
+                        quux: vec![
+                            QuuxFrom { id: String::from("y") },
+                            QuuxFrom { id: String::from("x") },
+                        ],
+                    },
+                ],
+            },
+        ],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    context
+}
+
+#[test]
+fn test_qualified() {
+    let mut context = setup();
+
+    let foo = Foo::convert(
+        FooFrom { qux: String::from("one/two"), quux: Some(String::from("four::two::x")) },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(foo.qux.index(), 0);
+    assert_eq!(foo.quux.unwrap().index(), 1);
+
+    let foo = Foo::convert(
+        FooFrom { qux: String::from("four/two"), quux: Some(String::from("one::two::x")) },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(foo.qux.index(), 1);
+    assert_eq!(foo.quux.unwrap().index(), 0);
+}
+
+#[test]
+fn test_errors() {
+    let mut context = setup();
+
+    let err =
+        Foo::convert(FooFrom { qux: String::from("one/five"), quux: None }, &mut context, &NoArgs)
+            .err()
+            .unwrap();
+    assert_eq!(err.to_string(), "qux: Unknown ID five in one/five");
+
+    let err = Foo::convert(
+        FooFrom { qux: String::from("two/one/five"), quux: None },
+        &mut context,
+        &NoArgs,
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.to_string(), "qux: Too many scopes in qualified ID two/one/five");
+
+    let err = Foo::convert(
+        FooFrom { qux: String::from("one/two"), quux: Some(String::from("two::x")) },
+        &mut context,
+        &NoArgs,
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.to_string(), "quux: Qualified ID two::x must start from a global ID");
+
+    // references without the separator are resolved in the current scope
+    let err = Foo::convert(FooFrom { qux: String::from("two"), quux: None }, &mut context, &NoArgs)
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "qux: Scope qualified::Bar is not an ancestor of qualified::Qux");
+}
@@ -0,0 +1,125 @@
+use xylem::{declare_schema, Id, Identifiable, SchemaExt, Session, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom, derive(Clone))]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Zone>,
+    spawns: Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom, derive(Clone))]
+struct Spawn {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Spawn>,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+}
+
+#[test]
+fn test_diff() {
+    let (old_zones, report) = Session::<Schema>::new()
+        .convert::<Vec<Zone>>(vec![
+            ZoneFrom {
+                id:     String::from("forest"),
+                spawns: vec![
+                    SpawnFrom { id: String::from("wolf") },
+                    SpawnFrom { id: String::from("bear") },
+                ],
+            },
+            ZoneFrom {
+                id:     String::from("cave"),
+                spawns: vec![SpawnFrom { id: String::from("bat") }],
+            },
+            ZoneFrom {
+                id:     String::from("desert"),
+                spawns: vec![SpawnFrom { id: String::from("snake") }],
+            },
+        ])
+        .unwrap();
+
+    let (zones, _, diff) = Session::<Schema>::new()
+        .reconvert::<Vec<Zone>, (Zone, Spawn)>(
+            vec![
+                ZoneFrom {
+                    id:     String::from("lake"),
+                    spawns: vec![SpawnFrom { id: String::from("fish") }],
+                },
+                ZoneFrom {
+                    id:     String::from("forest"),
+                    spawns: vec![
+                        SpawnFrom { id: String::from("bear") },
+                        SpawnFrom { id: String::from("fox") },
+                    ],
+                },
+                ZoneFrom {
+                    id:     String::from("dune"),
+                    spawns: vec![SpawnFrom { id: String::from("snake") }],
+                },
+            ],
+            &report,
+        )
+        .unwrap();
+
+    let zone_diff = diff.scope::<Schema, Zone>(&[]).unwrap();
+    assert_eq!(zone_diff.added(), &[String::from("lake")]);
+    assert_eq!(zone_diff.removed(), &[String::from("cave")]);
+    assert_eq!(zone_diff.renamed(), &[(String::from("desert"), String::from("dune"))]);
+    assert!(!zone_diff.is_unchanged());
+
+    assert_eq!(diff.remap::<Schema, Zone>(&[], old_zones[0].id), Some(zones[1].id));
+    assert_eq!(diff.remap::<Schema, Zone>(&[], old_zones[1].id), None);
+    assert_eq!(diff.remap::<Schema, Zone>(&[], old_zones[2].id), Some(zones[2].id));
+
+    // the spawns in forest are remapped to the new index of forest
+    let forest = diff.scope::<Schema, Spawn>(&[0]).unwrap();
+    assert_eq!(forest.new_parent().as_deref(), Some(&[1][..]));
+    assert_eq!(forest.added(), &[String::from("fox")]);
+    assert_eq!(forest.removed(), &[String::from("wolf")]);
+    assert!(forest.renamed().is_empty());
+    let bear = diff.remap::<Schema, Spawn>(&[0], old_zones[0].spawns[1].id);
+    assert_eq!(bear, Some(zones[1].spawns[0].id));
+
+    let cave = diff.scope::<Schema, Spawn>(&[1]).unwrap();
+    assert_eq!(cave.new_parent(), &None);
+    assert_eq!(cave.removed(), &[String::from("bat")]);
+
+    // renamed scopes are matched by index
+    let desert = diff.scope::<Schema, Spawn>(&[2]).unwrap();
+    assert_eq!(desert.new_parent().as_deref(), Some(&[2][..]));
+    assert!(desert.is_unchanged());
+
+    let lake =
+        diff.scopes::<Schema, Spawn>().iter().find(|scope| scope.old_parent().is_none()).unwrap();
+    assert_eq!(lake.new_parent().as_deref(), Some(&[0][..]));
+    assert_eq!(lake.added(), &[String::from("fish")]);
+}
+
+#[test]
+fn test_unchanged() {
+    let zones = vec![
+        ZoneFrom {
+            id:     String::from("forest"),
+            spawns: vec![SpawnFrom { id: String::from("wolf") }],
+        },
+        ZoneFrom { id: String::from("cave"), spawns: Vec::new() },
+    ];
+    let (_, report) = Session::<Schema>::new().convert::<Vec<Zone>>(zones.clone()).unwrap();
+
+    let (_, _, diff) =
+        Session::<Schema>::new().reconvert::<Vec<Zone>, (Zone, Spawn)>(zones, &report).unwrap();
+
+    assert!(diff.scopes::<Schema, Zone>().iter().all(|scope| scope.is_unchanged()));
+    assert!(diff.scopes::<Schema, Spawn>().iter().all(|scope| scope.is_unchanged()));
+}
@@ -0,0 +1,219 @@
+use std::any::TypeId;
+
+use xylem::id::GlobalIdStore;
+use xylem::{
+    declare_schema, Context, DefaultContext, Id, Identifiable, LayerSelector, LazyId, SchemaExt,
+    Xylem,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = FolderFrom)]
+struct Folder {
+    #[xylem(args(new = true, track = true))]
+    id:       Id<Schema, Folder>,
+    children: Vec<Folder>,
+    files:    Vec<File>,
+    link:     Option<Id<Schema, Folder>>,
+    next:     Option<LazyId<Schema, Folder>>,
+}
+
+impl Identifiable<Schema> for Folder {
+    type Scope = Folder;
+    fn id(&self) -> Id<Schema, Folder> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = FileFrom)]
+struct File {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, File>,
+}
+
+impl Identifiable<Schema> for File {
+    type Scope = Folder;
+    fn id(&self) -> Id<Schema, File> { self.id }
+}
+
+/// Converts the top-level folders in a manually started root `Folder` scope.
+fn convert(
+    context: &mut DefaultContext,
+    folders: Vec<FolderFrom>,
+) -> Result<Vec<Folder>, anyhow::Error> {
+    let scope = context.start_scope::<Folder>();
+    let ret = Vec::<Folder>::convert(folders, context, &Default::default());
+    context.end_scope(scope);
+    ret
+}
+
+#[test]
+fn test_nested_namespaces() {
+    let mut context = DefaultContext::default();
+
+    let x = FolderFrom {
+        id:       String::from("x"),
+        children: Vec::new(),
+        files:    vec![FileFrom { id: String::from("readme") }],
+        link:     None,
+        next:     Some(String::from("y")),
+    };
+    let b = FolderFrom {
+        id:       String::from("b"),
+        children: vec![FolderFrom {
+            id:       String::from("x"),
+            children: Vec::new(),
+            files:    Vec::new(),
+            link:     None,
+            next:     None,
+        }],
+        files:    Vec::new(),
+        link:     Some(String::from("a")),
+        next:     None,
+    };
+
+    let folders = convert(
+        &mut context,
+        vec![
+            FolderFrom {
+                id:       String::from("a"),
+                children: vec![
+                    x,
+                    FolderFrom {
+                        id:       String::from("y"),
+                        children: Vec::new(),
+                        files:    Vec::new(),
+                        link:     None,
+                        next:     None,
+                    },
+                ],
+                files:    vec![FileFrom { id: String::from("readme") }],
+                link:     None,
+                next:     None,
+            },
+            b,
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(folders[0].id.index(), 0);
+    assert_eq!(folders[1].id.index(), 1);
+    assert_eq!(folders[0].children[1].id.index(), 1);
+    assert_eq!(folders[1].children[0].id.index(), 0);
+    assert_eq!(folders[1].link, Some(folders[0].id));
+    assert_eq!(folders[0].files[0].id.index(), 0);
+    assert_eq!(folders[0].children[0].files[0].id.index(), 0);
+    assert_eq!(
+        folders[0].children[0].next.as_ref().map(LazyId::id),
+        Some(folders[0].children[1].id)
+    );
+
+    let store = context.get::<GlobalIdStore<Schema, Folder>>(TypeId::of::<()>()).unwrap();
+    assert_eq!(names(store.ids().get(&vec![]).unwrap().as_slice()), [Some("a"), Some("b")]);
+    assert_eq!(names(store.ids().get(&vec![0]).unwrap().as_slice()), [Some("x"), Some("y")]);
+    assert_eq!(names(store.ids().get(&vec![1]).unwrap().as_slice()), [Some("x")]);
+
+    let store = context.get::<GlobalIdStore<Schema, File>>(TypeId::of::<()>()).unwrap();
+    assert_eq!(names(store.ids().get(&vec![0]).unwrap().as_slice()), [Some("readme")]);
+    assert_eq!(names(store.ids().get(&vec![0, 0]).unwrap().as_slice()), [Some("readme")]);
+}
+
+#[test]
+fn test_duplicate_sibling() {
+    let mut context = DefaultContext::default();
+
+    let err = convert(
+        &mut context,
+        vec![FolderFrom {
+            id:       String::from("a"),
+            children: vec![
+                FolderFrom {
+                    id:       String::from("x"),
+                    children: Vec::new(),
+                    files:    Vec::new(),
+                    link:     None,
+                    next:     None,
+                },
+                FolderFrom {
+                    id:       String::from("x"),
+                    children: Vec::new(),
+                    files:    Vec::new(),
+                    link:     None,
+                    next:     None,
+                },
+            ],
+            files:    Vec::new(),
+            link:     None,
+            next:     None,
+        }],
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "[0].children[1].id: Duplicate ID x");
+}
+
+#[test]
+fn test_reference_resolves_among_siblings() {
+    let mut context = DefaultContext::default();
+
+    let b = FolderFrom {
+        id:       String::from("b"),
+        children: Vec::new(),
+        files:    Vec::new(),
+        link:     Some(String::from("x")),
+        next:     None,
+    };
+
+    let err = convert(
+        &mut context,
+        vec![
+            FolderFrom {
+                id:       String::from("a"),
+                children: vec![FolderFrom {
+                    id:       String::from("x"),
+                    children: Vec::new(),
+                    files:    Vec::new(),
+                    link:     None,
+                    next:     None,
+                }],
+                files:    Vec::new(),
+                link:     None,
+                next:     None,
+            },
+            b,
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "[1].link: Unknown ID x");
+}
+
+#[test]
+fn test_layer_selector() {
+    let mut context = DefaultContext::default();
+    let folder = TypeId::of::<Folder>();
+
+    let outer = context.start_scope::<Folder>();
+    let file = context.start_scope::<File>();
+    let inner = context.start_scope::<Folder>();
+
+    assert_eq!(context.find_layer(folder, LayerSelector::Nearest), Some(3));
+    assert_eq!(context.find_layer(folder, LayerSelector::Outermost), Some(1));
+    assert_eq!(context.find_layer(folder, LayerSelector::Nth(1)), Some(1));
+    assert_eq!(context.find_layer(folder, LayerSelector::Nth(2)), None);
+    assert_eq!(context.find_layer(folder, LayerSelector::Below(3)), Some(1));
+    assert_eq!(context.find_layer(folder, LayerSelector::Below(1)), None);
+
+    *context.try_get_mut_at::<u32, _>(1, Default::default).unwrap() = 1;
+    *context.get_mut::<u32, _>(folder, Default::default) = 3;
+    assert_eq!(context.get_at::<u32>(1), Some(&1));
+    assert_eq!(context.get::<u32>(folder), Some(&3));
+    assert_eq!(context.get_at::<u32>(2), None);
+    assert!(context.try_get_mut_at::<u32, _>(4, Default::default).is_none());
+
+    context.end_scope(inner);
+    context.end_scope(file);
+    context.end_scope(outer);
+}
+
+fn names(names: &[Option<String>]) -> Vec<Option<&str>> {
+    names.iter().map(Option::as_deref).collect()
+}
@@ -0,0 +1,99 @@
+use xylem::id::{IdRegistry, IdTable};
+use xylem::{declare_schema, DefaultContext, Id, Identifiable, NoArgs, SchemaExt, Session, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom)]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Zone>,
+    spawns: Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom)]
+struct Spawn {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Spawn>,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+}
+
+#[test]
+fn test_registry() {
+    let (zones, report) = Session::<Schema>::new()
+        .convert::<Vec<Zone>>(vec![
+            ZoneFrom {
+                id:     String::from("forest"),
+                spawns: vec![
+                    SpawnFrom { id: String::from("wolf") },
+                    SpawnFrom { id: String::from("bear") },
+                ],
+            },
+            ZoneFrom {
+                id:     String::from("cave"),
+                spawns: vec![
+                    SpawnFrom { id: String::from("bat") },
+                    SpawnFrom { id: String::from("wolf") },
+                ],
+            },
+        ])
+        .unwrap();
+
+    let registry = report.registry::<Zone>();
+    assert_eq!(registry.name(zones[1].id).map(String::as_str), Some("cave"));
+    assert_eq!(registry.lookup("forest"), Some(zones[0].id));
+    assert_eq!(registry.lookup("lake"), None);
+    let names: Vec<_> = registry.iter().map(|(id, name)| (id.index(), name.as_str())).collect();
+    assert_eq!(names, [(0, "forest"), (1, "cave")]);
+
+    let registry = report.registry::<Spawn>();
+    assert_eq!(registry.lookup_in(&[1], "wolf"), Some(zones[1].spawns[1].id));
+    assert_eq!(registry.name_in(&[0], zones[0].spawns[1].id).map(String::as_str), Some("bear"));
+    assert_eq!(registry.lookup("wolf"), None);
+    let names: Vec<_> = registry.iter_in(&[1]).map(|(_, name)| name).collect();
+    assert_eq!(names, ["bat", "wolf"]);
+    let scopes: Vec<_> = registry.scopes().collect();
+    assert_eq!(scopes, [&[0][..], &[1][..]]);
+}
+
+#[test]
+fn test_reserved() {
+    let mut table = IdTable::default();
+    let mut context = DefaultContext::default();
+    Vec::<Zone>::convert(
+        vec![
+            ZoneFrom { id: String::from("forest"), spawns: Vec::new() },
+            ZoneFrom { id: String::from("cave"), spawns: Vec::new() },
+        ],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    table.record::<Schema, Zone>(&context);
+
+    let mut context = DefaultContext::default();
+    table.seed::<Schema, Zone>(&mut context).unwrap();
+    let zones = Vec::<Zone>::convert(
+        vec![ZoneFrom { id: String::from("cave"), spawns: Vec::new() }],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    // the reserved index of forest has no name
+    let registry = IdRegistry::<Schema, Zone>::from_context(&context);
+    assert_eq!(registry.lookup("forest"), None);
+    assert_eq!(registry.name(Id::new(0)), None);
+    assert_eq!(registry.name(zones[0].id).map(String::as_str), Some("cave"));
+    assert_eq!(registry.iter().count(), 1);
+}
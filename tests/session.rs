@@ -0,0 +1,109 @@
+use std::any::TypeId;
+
+use xylem::{
+    declare_schema, Context, DefaultContext, Id, Identifiable, LazyId, SchemaExt, Session, Xylem,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Clone)]
+struct Limits {
+    max_level: u32,
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom)]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Zone>,
+    next:   Option<LazyId<Schema, Zone>>,
+    #[xylem(transform_with_context = level(u32))]
+    level:  u32,
+    spawns: Vec<Spawn>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = SpawnFrom)]
+struct Spawn {
+    #[xylem(args(new = true, track = true))]
+    id: Id<Schema, Spawn>,
+}
+
+impl Identifiable<Schema> for Spawn {
+    type Scope = Zone;
+    fn id(&self) -> Id<Schema, Spawn> { self.id }
+    fn report_unused() -> bool { false }
+}
+
+fn level(from: u32, context: &mut DefaultContext) -> anyhow::Result<u32> {
+    let max = context.get::<Limits>(TypeId::of::<()>()).map_or(u32::MAX, |limits| limits.max_level);
+    if from > max {
+        context.warn(format_args!("Level {} is capped at {}", from, max));
+        return Ok(max);
+    }
+    Ok(from)
+}
+
+#[test]
+fn test_report() {
+    let (zones, report) = Session::<Schema>::new()
+        .with_data(Limits { max_level: 10 })
+        .convert::<Vec<Zone>>(vec![
+            ZoneFrom {
+                id:     String::from("forest"),
+                next:   Some(String::from("cave")),
+                level:  5,
+                spawns: vec![
+                    SpawnFrom { id: String::from("wolf") },
+                    SpawnFrom { id: String::from("bear") },
+                ],
+            },
+            ZoneFrom {
+                id:     String::from("cave"),
+                next:   None,
+                level:  20,
+                spawns: vec![SpawnFrom { id: String::from("bat") }],
+            },
+        ])
+        .unwrap();
+
+    // root finalizers have been called
+    assert_eq!(zones[0].next.as_ref().unwrap().get(), Some(zones[1].id));
+    assert_eq!(zones[1].level, 10);
+
+    assert_eq!(names(report.names::<Zone>()), [Some("forest"), Some("cave")]);
+    assert_eq!(report.name(zones[1].id).map(String::as_str), Some("cave"));
+    assert_eq!(names(report.tracked::<Spawn>(&[0])), [Some("wolf"), Some("bear")]);
+    assert_eq!(zones[0].spawns[1].id.index(), 1);
+    assert_eq!(names(report.tracked::<Spawn>(&[1])), [Some("bat")]);
+    assert!(report.tracked::<Spawn>(&[2]).is_empty());
+    assert!(report.names::<Spawn>().is_empty());
+
+    let warnings: Vec<_> = report.warnings().iter().map(ToString::to_string).collect();
+    assert_eq!(warnings, ["[1]: Level 20 is capped at 10", "[0].id: Unused ID forest"]);
+
+    assert_eq!(report.get::<Limits>().map(|limits| limits.max_level), Some(10));
+}
+
+#[test]
+fn test_error() {
+    let err = Session::<Schema>::new()
+        .convert::<Vec<Zone>>(vec![ZoneFrom {
+            id:     String::from("forest"),
+            next:   Some(String::from("cave")),
+            level:  5,
+            spawns: Vec::new(),
+        }])
+        .err()
+        .expect("unresolved reference should fail");
+    assert_eq!(err.to_string(), "[0].next: Unknown ID cave");
+}
+
+fn names(names: &[Option<String>]) -> Vec<Option<&str>> {
+    names.iter().map(Option::as_deref).collect()
+}
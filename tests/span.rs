@@ -0,0 +1,168 @@
+use std::ops::Range;
+
+use xylem::{
+    declare_schema, Context, DefaultContext, Id, Identifiable, LineColumn, NoArgs, PathError,
+    SchemaExt, Spanned, Xylem,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = WorldFrom)]
+#[cfg_attr(feature = "serde", xylem(derive(serde::Deserialize)))]
+struct World {
+    zones: Vec<Zone>,
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ZoneFrom, spanned)]
+#[cfg_attr(feature = "serde", xylem(derive(serde::Deserialize)))]
+struct Zone {
+    #[xylem(args(new = true))]
+    id:        Id<Schema, Zone>,
+    neighbour: Option<Id<Schema, Zone>>,
+}
+
+impl Identifiable<Schema> for Zone {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Zone> { self.id }
+}
+
+#[test]
+fn test_spanned_fields() {
+    let mut context = DefaultContext::default();
+
+    let world = World::convert(
+        WorldFrom {
+            zones: vec![
+                ZoneFrom {
+                    id:        Spanned::new(String::from("forest"), 3..9),
+                    neighbour: Spanned::new(None, 10..10),
+                },
+                ZoneFrom {
+                    id:        Spanned::new(String::from("cave"), 20..24),
+                    neighbour: Spanned::new(Some(String::from("forest")), 30..36),
+                },
+            ],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert_eq!(world.zones[1].neighbour, Some(world.zones[0].id));
+}
+
+#[test]
+fn test_duplicate_span() {
+    let mut context = DefaultContext::default();
+
+    let err = World::convert(
+        WorldFrom {
+            zones: vec![
+                ZoneFrom {
+                    id:        Spanned::new(String::from("forest"), 3..9),
+                    neighbour: Spanned::new(None, 10..10),
+                },
+                ZoneFrom {
+                    id:        Spanned::new(String::from("forest"), 20..26),
+                    neighbour: Spanned::new(None, 30..30),
+                },
+            ],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "zones[1].id: Duplicate ID forest (declared at 3..9 and 20..26)");
+    assert_eq!(err.downcast_ref::<PathError>().unwrap().spans(), &[3..9, 20..26]);
+}
+
+#[test]
+fn test_unknown_span() {
+    let mut context = DefaultContext::default();
+
+    let err = World::convert(
+        WorldFrom {
+            zones: vec![ZoneFrom {
+                id:        Spanned::new(String::from("forest"), 3..9),
+                neighbour: Spanned::new(Some(String::from("cave")), 10..14),
+            }],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "zones[0].neighbour: Unknown ID cave at 10..14");
+    assert_eq!(err.downcast_ref::<PathError>().unwrap().spans(), &[Range { start: 10, end: 14 }]);
+}
+
+struct Position(Option<Range<usize>>);
+
+impl Xylem<Schema> for Position {
+    type From = ();
+    type Args = NoArgs;
+
+    fn convert_impl(
+        (): Self::From,
+        context: &mut DefaultContext,
+        _args: &Self::Args,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Position(context.span()))
+    }
+}
+
+#[test]
+fn test_context_span() {
+    let mut context = DefaultContext::default();
+
+    let position =
+        Spanned::<Position>::convert(Spanned::new((), 5..8), &mut context, &NoArgs).unwrap();
+    assert_eq!(position.span(), 5..8);
+    assert_eq!(position.value().0, Some(5..8));
+
+    let position = Position::convert((), &mut context, &NoArgs).unwrap();
+    assert_eq!(position.0, None);
+}
+
+#[test]
+fn test_line_column() {
+    let source = "forest\ncave = \"\u{e9}t\u{e9}\"\n";
+    assert_eq!(LineColumn::of(source, 0).to_string(), "1:1");
+    assert_eq!(LineColumn::of(source, 7).to_string(), "2:1");
+    assert_eq!(LineColumn::of(source, 17).to_string(), "2:10");
+    assert_eq!(LineColumn::of(source, 100).to_string(), "3:1");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_toml_span() {
+    let source = r#"
+[[zones]]
+id = "forest"
+neighbour = "cave"
+"#;
+    let from: WorldFrom = toml::from_str(source).unwrap();
+    assert_eq!(&source[from.zones[0].id.span()], "\"forest\"");
+
+    let mut context = DefaultContext::default();
+    let err = World::convert(from, &mut context, &NoArgs).unwrap_err();
+    assert_eq!(err.to_string(), "zones[0].neighbour: Unknown ID cave at 37..43");
+    let span = err.downcast_ref::<PathError>().unwrap().spans()[0].clone();
+    assert_eq!(LineColumn::of(source, span.start).to_string(), "4:13");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_json_without_spans() {
+    let source = r#"{"zones": [{"id": "forest", "neighbour": "cave"}]}"#;
+    assert!(serde_json::from_str::<WorldFrom>(source).is_err());
+
+    let from: WorldFrom =
+        xylem::deserialize_without_spans(|| serde_json::from_str(source)).unwrap();
+    assert_eq!(from.zones[0].id.span(), 0..0);
+
+    let mut context = DefaultContext::default();
+    let err = World::convert(from, &mut context, &NoArgs).unwrap_err();
+    assert_eq!(err.to_string(), "zones[0].neighbour: Unknown ID cave");
+    assert!(err.downcast_ref::<PathError>().unwrap().spans().is_empty());
+}
@@ -0,0 +1,136 @@
+use xylem::{
+    declare_schema, DefaultContext, Id, Identifiable, NoArgs, SchemaExt, Xylem, XylemInverse,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = TaggedFrom, derive(Debug, Clone, PartialEq), inverse)]
+struct Tagged {
+    #[xylem(args(new = true))]
+    id:    Id<Schema, Tagged>,
+    #[xylem(transform = parse_value(String), inverse = format_value)]
+    value: u32,
+}
+
+impl Identifiable<Schema> for Tagged {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Tagged> { self.id }
+}
+
+fn parse_value(value: String) -> anyhow::Result<u32> { Ok(value.parse()?) }
+
+fn format_value(value: u32) -> anyhow::Result<String> { Ok(value.to_string()) }
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = EntryFrom, derive(Debug, PartialEq), untagged, inverse)]
+enum Entry {
+    Number(Tagged),
+    Text {
+        #[xylem(transform = describe(TaggedFrom), inverse = undescribe)]
+        text: String,
+    },
+}
+
+fn describe(from: TaggedFrom) -> anyhow::Result<String> {
+    if from.value.is_empty() {
+        anyhow::bail!("empty value");
+    }
+    Ok(format!("{}={}", from.id, from.value))
+}
+
+fn undescribe(text: String) -> anyhow::Result<TaggedFrom> {
+    let (id, value) = text.split_once('=').unwrap();
+    Ok(TaggedFrom { id: id.to_string(), value: value.to_string() })
+}
+
+#[test]
+fn test_first_match() {
+    let mut context = DefaultContext::default();
+
+    let entry = Entry::convert(
+        EntryFrom(TaggedFrom { id: String::from("foo"), value: String::from("3") }),
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    match entry {
+        Entry::Number(tagged) => {
+            assert_eq!(tagged.id.index(), 0);
+            assert_eq!(tagged.value, 3);
+        }
+        Entry::Text { .. } => panic!("expected Number, got {:?}", entry),
+    }
+}
+
+#[test]
+fn test_rollback_failed_variant() {
+    let mut context = DefaultContext::default();
+
+    // `Number` declares the ID "bar" before failing to parse the value.
+    let entry = Entry::convert(
+        EntryFrom(TaggedFrom { id: String::from("bar"), value: String::from("three") }),
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    match &entry {
+        Entry::Text { text } => assert_eq!(text, "bar=three"),
+        Entry::Number(_) => panic!("expected Text, got {:?}", entry),
+    }
+
+    // The ID declared by the failed attempt has been rolled back.
+    let entry = Entry::convert(
+        EntryFrom(TaggedFrom { id: String::from("bar"), value: String::from("4") }),
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    match entry {
+        Entry::Number(tagged) => assert_eq!(tagged.id.index(), 0),
+        Entry::Text { .. } => panic!("expected Number, got {:?}", entry),
+    }
+}
+
+#[test]
+fn test_no_match() {
+    let mut context = DefaultContext::default();
+
+    let err = Entry::convert(
+        EntryFrom(TaggedFrom { id: String::from("qux"), value: String::from("") }),
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Number.0.value: cannot parse integer from empty string\nText.text: empty value"
+    );
+}
+
+#[test]
+fn test_inverse() {
+    let mut context = DefaultContext::default();
+
+    let number = Entry::convert(
+        EntryFrom(TaggedFrom { id: String::from("foo"), value: String::from("3") }),
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    let text = Entry::convert(
+        EntryFrom(TaggedFrom { id: String::from("bar"), value: String::from("three") }),
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    assert_eq!(
+        number.unconvert(&mut context, &NoArgs).unwrap(),
+        EntryFrom(TaggedFrom { id: String::from("foo"), value: String::from("3") })
+    );
+    assert_eq!(
+        text.unconvert(&mut context, &NoArgs).unwrap(),
+        EntryFrom(TaggedFrom { id: String::from("bar"), value: String::from("three") })
+    );
+}
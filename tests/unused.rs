@@ -0,0 +1,147 @@
+#![allow(dead_code)] // the types are only converted to test the warnings.
+
+use xylem::{
+    declare_schema, finalize, Context, DefaultContext, Id, Identifiable, LazyId, NoArgs, SchemaExt,
+    Xylem,
+};
+
+declare_schema!(Schema: SchemaExt);
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = CatalogFrom)]
+struct Catalog {
+    recipes: Vec<Recipe>,
+    items:   Vec<Item>,
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = RecipeFrom)]
+struct Recipe {
+    #[xylem(args(new = true))]
+    id:     Id<Schema, Recipe>,
+    output: LazyId<Schema, Item>,
+    steps:  Vec<Step>,
+}
+
+impl Identifiable<Schema> for Recipe {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Recipe> { self.id }
+    fn report_unused() -> bool { false }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = StepFrom)]
+struct Step {
+    #[xylem(args(new = true))]
+    id:   Id<Schema, Step>,
+    next: Option<Id<Schema, Step>>,
+}
+
+impl Identifiable<Schema> for Step {
+    type Scope = Recipe;
+    fn id(&self) -> Id<Schema, Step> { self.id }
+}
+
+#[derive(Debug, Xylem)]
+#[xylem(expose = ItemFrom)]
+struct Item {
+    #[xylem(args(new = true))]
+    id: Id<Schema, Item>,
+}
+
+impl Identifiable<Schema> for Item {
+    type Scope = ();
+    fn id(&self) -> Id<Schema, Item> { self.id }
+}
+
+fn messages(context: &mut DefaultContext) -> Vec<String> {
+    context.take_warnings().iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_unused() {
+    let mut context = DefaultContext::default();
+    Catalog::convert(
+        CatalogFrom {
+            recipes: vec![
+                RecipeFrom {
+                    id:     String::from("forge"),
+                    output: String::from("sword"),
+                    steps:  vec![
+                        StepFrom { id: String::from("a"), next: None },
+                        StepFrom { id: String::from("b"), next: Some(String::from("a")) },
+                        StepFrom { id: String::from("c"), next: None },
+                    ],
+                },
+                RecipeFrom {
+                    id:     String::from("smith"),
+                    output: String::from("sword"),
+                    steps:  Vec::new(),
+                },
+            ],
+            items:   vec![
+                ItemFrom { id: String::from("sword") },
+                ItemFrom { id: String::from("bow") },
+                ItemFrom { id: String::from("arrow") },
+            ],
+        },
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+    assert!(context.warnings().is_empty());
+
+    finalize::<Schema>(&mut context).unwrap();
+    assert_eq!(
+        messages(&mut context),
+        [
+            "recipes[0].steps[1].id: Unused ID b",
+            "recipes[0].steps[2].id: Unused ID c",
+            "items[1].id: Unused ID bow",
+            "items[2].id: Unused ID arrow",
+        ]
+    );
+
+    // unused IDs are only reported once
+    finalize::<Schema>(&mut context).unwrap();
+    assert!(context.warnings().is_empty());
+
+    Vec::<Item>::convert(vec![ItemFrom { id: String::from("shield") }], &mut context, &NoArgs)
+        .unwrap();
+    Id::<Schema, Item>::convert(String::from("bow"), &mut context, &Default::default()).unwrap();
+    finalize::<Schema>(&mut context).unwrap();
+    assert_eq!(messages(&mut context), ["[0].id: Unused ID shield"]);
+}
+
+#[test]
+fn test_rollback_references() {
+    let mut context = DefaultContext::default();
+    Vec::<Item>::convert(
+        vec![ItemFrom { id: String::from("sword") }, ItemFrom { id: String::from("bow") }],
+        &mut context,
+        &NoArgs,
+    )
+    .unwrap();
+
+    let checkpoint = context.checkpoint();
+    Id::<Schema, Item>::convert(String::from("bow"), &mut context, &Default::default()).unwrap();
+    context.rollback(checkpoint);
+    Id::<Schema, Item>::convert(String::from("sword"), &mut context, &Default::default()).unwrap();
+
+    finalize::<Schema>(&mut context).unwrap();
+    assert_eq!(messages(&mut context), ["[1].id: Unused ID bow"]);
+}
+
+#[test]
+fn test_rollback_declarations() {
+    let mut context = DefaultContext::default();
+    let checkpoint = context.checkpoint();
+    Vec::<Item>::convert(vec![ItemFrom { id: String::from("shield") }], &mut context, &NoArgs)
+        .unwrap();
+    context.rollback(checkpoint);
+    Vec::<Item>::convert(vec![ItemFrom { id: String::from("sword") }], &mut context, &NoArgs)
+        .unwrap();
+
+    finalize::<Schema>(&mut context).unwrap();
+    assert_eq!(messages(&mut context), ["[0].id: Unused ID sword"]);
+}
@@ -0,0 +1,66 @@
+use xylem::{declare_schema, Context, DefaultContext, NoArgs, Processable, SchemaExt, Xylem};
+
+declare_schema!(Schema: SchemaExt);
+
+struct Percent(u32);
+
+impl Xylem<Schema> for Percent {
+    type From = u32;
+    type Args = NoArgs;
+
+    fn convert_impl(
+        from: Self::From,
+        context: &mut DefaultContext,
+        _args: &Self::Args,
+    ) -> Result<Self, anyhow::Error> {
+        if from > 100 {
+            context.warn(format_args!("{}% is more than 100%", from));
+        }
+        Ok(Percent(from))
+    }
+}
+
+#[derive(Xylem)]
+#[xylem(process, expose = FooFrom)]
+struct Foo {
+    #[xylem(preserve)]
+    #[allow(dead_code)] // it's only used to test the deprecation warning.
+    old: Option<u32>,
+    chance: Vec<Percent>,
+}
+
+impl Processable<Schema> for Foo {
+    fn preprocess(from: &mut Self::From, context: &mut DefaultContext) -> anyhow::Result<()> {
+        if from.old.is_some() {
+            context.warn("`old` is deprecated");
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_warnings() {
+    let mut context = DefaultContext::default();
+
+    let foo = Foo::convert(FooFrom { old: Some(1), chance: vec![50, 150] }, &mut context, &NoArgs)
+        .unwrap();
+    assert_eq!(foo.chance[1].0, 150);
+
+    let messages: Vec<_> = context.warnings().iter().map(|warning| warning.to_string()).collect();
+    assert_eq!(messages, ["`old` is deprecated", "chance[1]: 150% is more than 100%"]);
+
+    assert_eq!(context.take_warnings().len(), 2);
+    assert!(context.warnings().is_empty());
+}
+
+#[test]
+fn test_rollback_warnings() {
+    let mut context = DefaultContext::default();
+
+    let result = context.transaction(|context| {
+        Foo::convert(FooFrom { old: Some(1), chance: Vec::new() }, context, &NoArgs)?;
+        Err::<(), _>(anyhow::anyhow!("rejected"))
+    });
+    assert!(result.is_err());
+    assert!(context.warnings().is_empty());
+}